@@ -0,0 +1,70 @@
+# English strings of the nightmarish forest
+# Format: `key = value`, indented lines continue value,
+# `{ " " }` keeps spaces and `{ $name }` is a variable.
+
+## HUD
+hud-fps = FPS:{ " " }
+
+## Settings menu
+settings-resolution = Resolution
+settings-fullscreen = Fullscreen
+settings-vsync = VSync
+settings-fov = Field of view
+settings-shadows = Shadows
+settings-sensitivity = Mouse sensitivity
+settings-invert-y = Invert mouse
+settings-master-volume = Master volume
+settings-sfx-volume = Effects volume
+settings-ambience-volume = Ambience volume
+settings-music-volume = Music volume
+settings-voice-volume = Voice volume
+settings-language = Language
+settings-on = On
+settings-off = Off
+settings-shadows-off = Off
+settings-shadows-low = Low
+settings-shadows-medium = Medium
+settings-shadows-high = High
+
+## Languages
+language-english = English
+language-russian = Русский
+
+## Interaction
+interaction-prompt = [{ $key }] { $action }
+interact-pick-up = Pick up
+interact-switch = Switch
+toast-picked-up = Picked up: { $item }
+
+## Items
+item-cube = Strange cube
+
+## Subtitles
+subtitle-first-encounter-1 = What... what is that between the trees?
+subtitle-first-encounter-2 = I have to get out of this forest.
+
+## Objectives
+objective-find-cube = Find the strange cube
+objective-reach-forest-edge = Reach the edge of the forest
+objective-open-gate = Open the old gate
+journal-active = Current objectives
+journal-completed = Completed
+
+## Notes
+interact-read = Read
+note-close = [E] Close
+journal-notes = Notes ({ $unread } unread)
+inventory-title = Inventory
+inventory-empty = Nothing here yet
+inventory-journal = Journal
+note-diary-title = Torn diary page
+note-diary-text =
+    October 12.
+    The lights in the forest came back again last night.
+    I counted them from the window: seven, then eight.
+    Nobody in the village believes me. I will go and look myself.
+
+## Levels
+loading-level = Loading: { $level }...
+level-test = Test level
+level-spawn = Forest clearing
@@ -0,0 +1,70 @@
+# Русские строки игры the nightmarish forest
+# Формат: `ключ = значение`, строки с отступом продолжают значение,
+# `{ " " }` сохраняет пробелы, а `{ $name }` - это переменная.
+
+## HUD
+hud-fps = FPS:{ " " }
+
+## Меню настроек
+settings-resolution = Разрешение
+settings-fullscreen = Полный экран
+settings-vsync = Вертикальная синхронизация
+settings-fov = Поле зрения
+settings-shadows = Тени
+settings-sensitivity = Чувствительность мыши
+settings-invert-y = Инверсия мыши
+settings-master-volume = Общая громкость
+settings-sfx-volume = Громкость эффектов
+settings-ambience-volume = Громкость окружения
+settings-music-volume = Громкость музыки
+settings-voice-volume = Громкость голосов
+settings-language = Язык
+settings-on = Вкл
+settings-off = Выкл
+settings-shadows-off = Выкл
+settings-shadows-low = Низкое
+settings-shadows-medium = Среднее
+settings-shadows-high = Высокое
+
+## Языки
+language-english = English
+language-russian = Русский
+
+## Взаимодействие
+interaction-prompt = [{ $key }] { $action }
+interact-pick-up = Подобрать
+interact-switch = Переключить
+toast-picked-up = Подобрано: { $item }
+
+## Предметы
+item-cube = Странный куб
+
+## Субтитры
+subtitle-first-encounter-1 = Что... что это там, между деревьями?
+subtitle-first-encounter-2 = Мне нужно выбраться из этого леса.
+
+## Задачи
+objective-find-cube = Найти странный куб
+objective-reach-forest-edge = Добраться до края леса
+objective-open-gate = Открыть старые ворота
+journal-active = Текущие задачи
+journal-completed = Выполнено
+
+## Записки
+interact-read = Прочитать
+note-close = [E] Закрыть
+journal-notes = Записки (непрочитанных: { $unread })
+inventory-title = Инвентарь
+inventory-empty = Пока ничего нет
+inventory-journal = Журнал
+note-diary-title = Вырванная страница дневника
+note-diary-text =
+    12 октября.
+    Этой ночью огни в лесу снова вернулись.
+    Я считал их из окна: семь, потом восемь.
+    В деревне мне никто не верит. Пойду и посмотрю сам.
+
+## Уровни
+loading-level = Загрузка: { $level }...
+level-test = Тестовый уровень
+level-spawn = Лесная поляна
@@ -0,0 +1,33 @@
+// terrain material: mixes colors of ground layers by splat map
+#import bevy_pbr::{
+    pbr_fragment::pbr_input_from_standard_material,
+    pbr_functions::{alpha_discard, apply_pbr_lighting, main_pass_post_lighting_processing},
+    forward_io::{VertexOutput, FragmentOutput},
+}
+
+@group(2) @binding(100) var<uniform> layer_colors: array<vec4<f32>, 4>;
+@group(2) @binding(101) var splat_texture: texture_2d<f32>;
+@group(2) @binding(102) var splat_sampler: sampler;
+
+@fragment
+fn fragment(
+    in: VertexOutput,
+    @builtin(front_facing) is_front: bool,
+) -> FragmentOutput {
+    var pbr_input = pbr_input_from_standard_material(in, is_front);
+
+    // weights of layers are normalized, so sum of channels can be any
+    let weights = textureSample(splat_texture, splat_sampler, in.uv);
+    let total = max(weights.r + weights.g + weights.b + weights.a, 0.0001);
+    let color = (layer_colors[0] * weights.r
+        + layer_colors[1] * weights.g
+        + layer_colors[2] * weights.b
+        + layer_colors[3] * weights.a) / total;
+
+    pbr_input.material.base_color = alpha_discard(pbr_input.material, pbr_input.material.base_color * color);
+
+    var out: FragmentOutput;
+    out.color = apply_pbr_lighting(pbr_input);
+    out.color = main_pass_post_lighting_processing(pbr_input, out.color);
+    return out;
+}
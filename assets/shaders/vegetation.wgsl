@@ -0,0 +1,64 @@
+// instanced vegetation: wind sway, distance fade and standard lighting
+#import bevy_pbr::{
+    mesh_view_bindings::{globals, view},
+    view_transformations::position_world_to_clip,
+    pbr_types,
+    pbr_functions,
+}
+#import "shaders/vegetation_wind.wgsl"::{instance_position, rotate_y}
+
+struct Vertex {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    // position (xyz) and scale (w)
+    @location(8) i_position_scale: vec4<f32>,
+    @location(9) i_color: vec4<f32>,
+    // rotation, wind, fade start, fade end
+    @location(10) i_params: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) world_position: vec3<f32>,
+    @location(1) world_normal: vec3<f32>,
+    @location(2) color: vec4<f32>,
+    @location(3) fade: f32,
+};
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    let rotation = vertex.i_params.x;
+    let world_position = instance_position(vertex.position, vertex.i_position_scale, vertex.i_params, globals.time);
+    let distance = length(world_position - view.world_position);
+
+    var out: VertexOutput;
+    out.clip_position = position_world_to_clip(world_position);
+    out.world_position = world_position;
+    out.world_normal = rotate_y(vertex.normal, rotation);
+    out.color = vertex.i_color;
+    out.fade = clamp((vertex.i_params.w - distance) / max(vertex.i_params.w - vertex.i_params.z, 0.001), 0.0, 1.0);
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput, @builtin(front_facing) is_front: bool) -> @location(0) vec4<f32> {
+    // dithered fade, so depth can be written
+    let dither = fract(52.9829189 * fract(dot(in.clip_position.xy, vec2<f32>(0.06711056, 0.00583715))));
+    if (in.fade < dither) {
+        discard;
+    }
+
+    var pbr_input = pbr_types::pbr_input_new();
+    pbr_input.material.base_color = in.color;
+    pbr_input.material.perceptual_roughness = 0.9;
+    pbr_input.material.flags = pbr_types::STANDARD_MATERIAL_FLAGS_FOG_ENABLED_BIT;
+    pbr_input.frag_coord = in.clip_position;
+    pbr_input.world_position = vec4<f32>(in.world_position, 1.0);
+    pbr_input.world_normal = normalize(select(-in.world_normal, in.world_normal, is_front));
+    pbr_input.N = pbr_input.world_normal;
+    pbr_input.V = pbr_functions::calculate_view(pbr_input.world_position, pbr_input.is_orthographic);
+
+    var color = pbr_functions::apply_pbr_lighting(pbr_input);
+    color = pbr_functions::main_pass_post_lighting_processing(pbr_input, color);
+    return color;
+}
@@ -0,0 +1,22 @@
+// depth of instanced vegetation for shadow maps
+#import bevy_render::{view::View, globals::Globals}
+#import "shaders/vegetation_wind.wgsl"::instance_position
+
+// bindings of prepass view
+@group(0) @binding(0) var<uniform> view: View;
+@group(0) @binding(1) var<uniform> globals: Globals;
+
+struct Vertex {
+    @location(0) position: vec3<f32>,
+    // position (xyz) and scale (w)
+    @location(8) i_position_scale: vec4<f32>,
+    @location(9) i_color: vec4<f32>,
+    // rotation, wind, fade start, fade end
+    @location(10) i_params: vec4<f32>,
+};
+
+@vertex
+fn vertex(vertex: Vertex) -> @builtin(position) vec4<f32> {
+    let world_position = instance_position(vertex.position, vertex.i_position_scale, vertex.i_params, globals.time);
+    return view.clip_from_world * vec4<f32>(world_position, 1.0);
+}
@@ -0,0 +1,25 @@
+// placement of vegetation instance with wind sway, it's shared by main and shadow passes
+
+// direction and speed of wind
+const WIND_DIRECTION: vec2<f32> = vec2<f32>(0.8, 0.6);
+const WIND_SPEED: f32 = 1.3;
+
+// rotate vector around Y
+fn rotate_y(vector: vec3<f32>, angle: f32) -> vec3<f32> {
+    let c = cos(angle);
+    let s = sin(angle);
+    return vec3<f32>(vector.x * c + vector.z * s, vector.y, -vector.x * s + vector.z * c);
+}
+
+// return position of vertex in world
+fn instance_position(position: vec3<f32>, position_scale: vec4<f32>, params: vec4<f32>, time: f32) -> vec3<f32> {
+    var local = rotate_y(position * position_scale.w, params.x);
+
+    // top of mesh sways more, every instance has own phase
+    let phase = dot(position_scale.xz, vec2<f32>(0.13, 0.17));
+    let gust = sin(time * WIND_SPEED + phase) + 0.4 * sin(time * WIND_SPEED * 2.7 + phase * 1.9);
+    let sway = gust * params.y * max(local.y, 0.0);
+    local += vec3<f32>(WIND_DIRECTION.x, 0.0, WIND_DIRECTION.y) * sway;
+
+    return local + position_scale.xyz;
+}
@@ -0,0 +1,25 @@
+//! Atmosphere - fog, ambient light and light shafts of locations.
+//! Profiles are defined in data (`assets/atmosphere/`), level has
+//! own profile and zones blend other profiles in.
+
+// import crates
+use bevy::prelude::*;
+
+use crate::ron_loader::RonLoader;
+
+// atmosphere systems
+pub(super) mod systems;
+pub mod structures;
+
+pub struct AtmospherePlugin;
+impl Plugin for AtmospherePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<structures::AtmosphereProfiles>();
+        app.register_asset_loader(RonLoader::<structures::AtmosphereProfiles>::new(&["atmosphere.ron"]));
+        app.register_type::<structures::AtmosphereZone>();
+        app.insert_resource(structures::AtmosphereModifiers::default());
+
+        app.add_systems(Startup, systems::setup_atmosphere);
+        app.add_systems(Update, (systems::blend_atmosphere, systems::apply_atmosphere).chain());
+    }
+}
@@ -0,0 +1,107 @@
+//! This module store atmosphere's structures and data
+use bevy::{prelude::*, utils::HashMap};
+use educe::Educe;
+use serde::Deserialize;
+
+use crate::components::box_zone_weight;
+
+// const's
+/// path to atmosphere profiles
+pub(super) const ATMOSPHERE_PROFILES: &str = "atmosphere/profiles.atmosphere.ron";
+/// profile of levels without own profile
+pub(super) const DEFAULT_PROFILE: &str = "default";
+/// speed of blending to target profile
+pub(super) const ATMOSPHERE_BLEND_SPEED: f32 = 1.5;
+/// size of volumetric fog around player
+pub(super) const FOG_VOLUME_SIZE: Vec3 = Vec3::new(80.0, 24.0, 80.0);
+
+/// Profile of atmosphere
+#[derive(Clone, Debug, Deserialize)]
+pub struct AtmosphereProfile {
+    /// color of distance fog
+    pub fog_color: [f32; 3],
+    /// density of distance fog (exponential squared)
+    pub fog_density: f32,
+    /// color of ambient light
+    pub ambient_color: [f32; 3],
+    /// brightness of ambient light
+    pub ambient_brightness: f32,
+    /// density of volumetric fog around player, zero - no fog
+    #[serde(default)]
+    pub volumetric_density: f32,
+    /// lights cast shafts through volumetric fog
+    #[serde(default)]
+    pub light_shafts: bool,
+}
+
+impl AtmosphereProfile {
+    /// Return profile between self and other
+    pub fn lerp(&self, other: &AtmosphereProfile, t: f32) -> AtmosphereProfile {
+        let mix = |a: [f32; 3], b: [f32; 3]| Vec3::from_array(a).lerp(Vec3::from_array(b), t).to_array();
+
+        AtmosphereProfile {
+            fog_color: mix(self.fog_color, other.fog_color),
+            fog_density: self.fog_density.lerp(other.fog_density, t),
+            ambient_color: mix(self.ambient_color, other.ambient_color),
+            ambient_brightness: self.ambient_brightness.lerp(other.ambient_brightness, t),
+            volumetric_density: self.volumetric_density.lerp(other.volumetric_density, t),
+            light_shafts: if t < 0.5 { self.light_shafts } else { other.light_shafts },
+        }
+    }
+}
+
+/// All profiles, they are loaded from `*.atmosphere.ron`
+#[derive(Asset, TypePath, Clone, Debug, Deserialize)]
+pub struct AtmosphereProfiles {
+    pub profiles: HashMap<String, AtmosphereProfile>,
+}
+
+/// Current state of atmosphere
+#[derive(Resource)]
+pub struct Atmosphere {
+    pub profiles: Handle<AtmosphereProfiles>,
+    /// blended profile, it's applied to world
+    pub current: Option<AtmosphereProfile>,
+}
+
+/// Multipliers of current profile, they are set by other systems (for example weather)
+#[derive(Resource, Clone, Copy, Debug, Educe)]
+#[educe(Default)]
+pub struct AtmosphereModifiers {
+    #[educe(Default = 1.0)] /// multiplier of ambient brightness
+    pub ambient_scale: f32,
+
+    #[educe(Default = 1.0)] /// multiplier of distance fog density
+    pub fog_density_scale: f32,
+
+    #[educe(Default = 1.0)] /// multiplier of volumetric fog density
+    pub volumetric_scale: f32,
+}
+
+/// Zone, inside which other profile is used (for example interior of house).
+/// It can be placed in blender.
+#[derive(Component, Clone, Debug, Educe, Reflect)]
+#[reflect(Component)]
+#[educe(Default)]
+#[require(Transform)]
+pub struct AtmosphereZone {
+    /// name of profile
+    pub profile: String,
+
+    #[educe(Default = Vec3::ONE)] /// half size of zone's box
+    pub half_extents: Vec3,
+
+    #[educe(Default = 4.0)] /// distance of blending outside of zone's box
+    pub fade_distance: f32,
+}
+
+impl AtmosphereZone {
+    /// Return weight of zone in point: 1.0 - inside, 0.0 - far outside
+    pub fn weight(&self, zone_transform: &GlobalTransform, point: Vec3) -> f32 {
+        box_zone_weight(self.half_extents, self.fade_distance, zone_transform, point)
+    }
+}
+
+/// Volumetric fog around player
+#[derive(Component)]
+pub struct AtmosphereFogVolume;
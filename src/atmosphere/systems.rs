@@ -0,0 +1,164 @@
+//! implementation of atmosphere systems
+
+// import crates
+use bevy::{pbr::{FogVolume, VolumetricFog, VolumetricLight}, prelude::*, utils::HashSet};
+
+// import data from this crate
+use crate::levels::structures::{LevelManager, LevelRegistry};
+use crate::player::structures::PlayerComponent;
+use super::structures::*;
+
+/// load profiles and create volumetric fog around player
+pub fn setup_atmosphere(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    commands.insert_resource(Atmosphere {
+        profiles: asset_server.load(ATMOSPHERE_PROFILES),
+        current: None,
+    });
+
+    commands.spawn((
+        #[cfg(debug_assertions)] Name::new("Atmosphere fog"),
+        FogVolume { density_factor: 0.0, ..default() },
+        Transform::from_scale(FOG_VOLUME_SIZE),
+        Visibility::Hidden,
+        AtmosphereFogVolume,
+    ));
+}
+
+/// blend current profile to profile of level and zones around player,
+/// every unknown profile is reported once (and again after reload)
+#[allow(clippy::too_many_arguments)]
+pub fn blend_atmosphere(
+    mut atmosphere: ResMut<Atmosphere>,
+    mut warned: Local<HashSet<String>>,
+    mut profile_events: EventReader<AssetEvent<AtmosphereProfiles>>,
+    zones_query: Query<(&AtmosphereZone, &GlobalTransform)>,
+    player_query: Query<&GlobalTransform, With<PlayerComponent>>,
+    profiles: Res<Assets<AtmosphereProfiles>>,
+    registries: Res<Assets<LevelRegistry>>,
+    manager: Res<LevelManager>,
+    time: Res<Time>,
+) {
+    if profile_events.read().count() > 0 {
+        warned.clear();
+    }
+
+    let Some(profiles) = profiles.get(&atmosphere.profiles) else { return; };
+
+    // profile of level
+    let level_profile = registries.get(&manager.registry)
+        .and_then(|registry| registry.get(manager.current.as_deref()?))
+        .and_then(|level| level.atmosphere.as_deref())
+        .unwrap_or(DEFAULT_PROFILE);
+
+    let Some(mut target) = profiles.profiles.get(level_profile).cloned() else {
+        warn_unknown_profile(&mut warned, level_profile);
+        return;
+    };
+
+    // zones are blended in over profile of level
+    if let Ok(player_transform) = player_query.get_single() {
+        for (zone, zone_transform) in zones_query.iter() {
+            let weight = zone.weight(zone_transform, player_transform.translation());
+            if weight <= 0.0 {
+                continue;
+            }
+
+            match profiles.profiles.get(&zone.profile) {
+                Some(profile) => target = target.lerp(profile, weight),
+                None => warn_unknown_profile(&mut warned, &zone.profile),
+            }
+        }
+    }
+
+    let t = 1.0 - (-ATMOSPHERE_BLEND_SPEED * time.delta_secs()).exp();
+    atmosphere.current = Some(match &atmosphere.current {
+        Some(current) => current.lerp(&target, t),
+        None => target,
+    });
+}
+
+/// apply current profile to cameras, lights and fog volume
+#[allow(clippy::type_complexity)]
+pub fn apply_atmosphere(
+    mut commands: Commands,
+    mut cameras_query: Query<(Entity, Option<&mut DistanceFog>, Option<&mut VolumetricFog>), With<Camera3d>>,
+    mut volume_query: Query<(&mut FogVolume, &mut Transform, &mut Visibility), With<AtmosphereFogVolume>>,
+    mut ambient_light: ResMut<AmbientLight>,
+    lights_query: Query<(Entity, Has<VolumetricLight>), Or<(With<DirectionalLight>, With<SpotLight>)>>,
+    player_query: Query<&GlobalTransform, With<PlayerComponent>>,
+    modifiers: Res<AtmosphereModifiers>,
+    atmosphere: Res<Atmosphere>,
+) {
+    let Some(profile) = &atmosphere.current else { return; };
+
+    let ambient_brightness = profile.ambient_brightness * modifiers.ambient_scale;
+    let fog_density = profile.fog_density * modifiers.fog_density_scale;
+    let volumetric_density = profile.volumetric_density * modifiers.volumetric_scale;
+
+    let fog_color = Color::srgb_from_array(profile.fog_color);
+    let ambient_color = Color::srgb_from_array(profile.ambient_color);
+
+    ambient_light.color = ambient_color;
+    ambient_light.brightness = ambient_brightness;
+
+    for (entity, distance_fog, volumetric_fog) in cameras_query.iter_mut() {
+        let falloff = FogFalloff::ExponentialSquared { density: fog_density };
+
+        match distance_fog {
+            Some(mut fog) => {
+                fog.color = fog_color;
+                fog.falloff = falloff;
+            },
+            None => {
+                commands.entity(entity).insert(DistanceFog { color: fog_color, falloff, ..default() });
+            },
+        }
+
+        // ambient part of volumetric fog is lit by ambient light
+        match volumetric_fog {
+            Some(mut fog) => {
+                fog.ambient_color = ambient_color;
+                fog.ambient_intensity = ambient_brightness / 500.0;
+            },
+            None => {
+                commands.entity(entity).insert(VolumetricFog { ambient_color, ..default() });
+            },
+        }
+    }
+
+    // volumetric fog follows player
+    let player_position = player_query.get_single().map(|transform| transform.translation()).ok();
+    for (mut volume, mut transform, mut visibility) in volume_query.iter_mut() {
+        volume.density_factor = volumetric_density;
+        volume.fog_color = fog_color;
+
+        if let Some(position) = player_position {
+            transform.translation = position;
+        }
+
+        let visible = volumetric_density > 0.0 && player_position.is_some();
+        visibility.set_if_neq(if visible { Visibility::Inherited } else { Visibility::Hidden });
+    }
+
+    // light shafts
+    for (entity, has_shafts) in lights_query.iter() {
+        if profile.light_shafts && !has_shafts {
+            commands.entity(entity).insert(VolumetricLight);
+        } else if !profile.light_shafts && has_shafts {
+            commands.entity(entity).remove::<VolumetricLight>();
+        }
+    }
+}
+
+// helper functions
+/// Warn about unknown profile, if it wasn't reported yet
+#[inline]
+fn warn_unknown_profile(warned: &mut HashSet<String>, profile: &str) {
+    if !warned.contains(profile) {
+        warned.insert(profile.to_string());
+        warn!("atmosphere profile \"{profile}\" doesn't exist");
+    }
+}
@@ -0,0 +1,37 @@
+//! Audio - in this module located a sound logic of game:
+//! mixer buses, spatial emitters, player's body sounds,
+//! ambience zones and music director
+
+// import crates
+use bevy::prelude::*;
+
+// audio systems
+pub(super) mod systems;
+pub mod structures;
+
+pub struct GameAudioPlugin;
+impl Plugin for GameAudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<structures::AudioMixer>();
+        app.register_type::<structures::MusicDirector>();
+        app.register_type::<structures::AmbienceZone>();
+
+        app.insert_resource(structures::AudioMixer::default());
+        app.insert_resource(structures::MusicDirector::default());
+        app.add_event::<structures::PlayStinger>();
+
+        app.add_systems(Startup, (systems::load_footsteps, systems::setup_music));
+        app.add_systems(Update, (
+            systems::setup_body_sounds,
+            systems::play_footsteps,
+            systems::update_body_sounds,
+            systems::setup_ambience_zones,
+            systems::update_ambience,
+            systems::update_chase,
+            systems::update_music,
+            systems::play_triggered_stingers,
+            systems::play_stingers,
+            systems::apply_mixer,
+        ).chain());
+    }
+}
@@ -0,0 +1,223 @@
+//! This module store audio's structures, enums and consts
+use bevy::{asset::io::file::FileAssetReader, audio::{PlaybackMode, Volume}, prelude::*, utils::HashMap};
+use educe::Educe;
+
+use crate::components::{box_zone_weight, SurfaceType};
+
+// const's
+/// distance (in meters) between two player's steps
+pub(super) const FOOTSTEP_STRIDE: f32 = 1.6;
+/// count of sound variations for every surface
+pub(super) const FOOTSTEP_VARIATIONS: usize = 4;
+/// folder of assets (the same as `AssetPlugin::file_path`)
+const ASSETS_FOLDER: &str = "assets";
+
+/// Loading of sounds, which can be not added to assets yet
+pub trait LoadSound {
+    /// Load sound if it's file exists, so missing
+    /// sounds are silent and don't log load errors
+    fn load_sound(&self, path: impl Into<String>) -> Option<Handle<AudioSource>>;
+}
+
+impl LoadSound for AssetServer {
+    fn load_sound(&self, path: impl Into<String>) -> Option<Handle<AudioSource>> {
+        let path = path.into();
+
+        if !FileAssetReader::get_base_path().join(ASSETS_FOLDER).join(&path).exists() {
+            debug!("sound \"{path}\" doesn't exist");
+            return None;
+        }
+
+        Some(self.load(path))
+    }
+}
+
+/// Mixer buses, every sound belongs to one bus
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum MixerBus {
+    #[default]
+    Sfx,
+    Ambience,
+    Music,
+    Voice,
+}
+
+/// Volumes of mixer's buses, final volume of sound
+/// is `master * bus * emitter`
+#[derive(Resource, Clone, Debug, Educe, Reflect)]
+#[reflect(Resource)]
+#[educe(Default)]
+pub struct AudioMixer {
+    #[educe(Default = 1.0)] /// volume of all sounds
+    pub master: f32,
+
+    #[educe(Default = 1.0)] /// volume of effects
+    pub sfx: f32,
+
+    #[educe(Default = 1.0)] /// volume of ambience
+    pub ambience: f32,
+
+    #[educe(Default = 1.0)] /// volume of music
+    pub music: f32,
+
+    #[educe(Default = 1.0)] /// volume of voices (and player's body)
+    pub voice: f32,
+}
+
+impl AudioMixer {
+    /// Return final volume of bus
+    pub fn volume(&self, bus: MixerBus) -> f32 {
+        let bus_volume = match bus {
+            MixerBus::Sfx => self.sfx,
+            MixerBus::Ambience => self.ambience,
+            MixerBus::Music => self.music,
+            MixerBus::Voice => self.voice,
+        };
+
+        self.master * bus_volume
+    }
+}
+
+/// Sound emitter, mixer controls volume of every entity with it
+#[derive(Component, Clone, Copy, Debug, Reflect)]
+#[reflect(Component)]
+pub struct AudioEmitter {
+    /// bus of this sound
+    pub bus: MixerBus,
+    /// own volume of sound
+    pub volume: f32,
+}
+
+impl AudioEmitter {
+    /// Create emitter on bus with volume
+    pub fn new(bus: MixerBus, volume: f32) -> Self {
+        Self { bus, volume }
+    }
+
+    /// Return bundle of sound attached to entity with this emitter.
+    /// Spatial sounds are positioned by entity's transform.
+    pub fn sound(
+        self,
+        source: Handle<AudioSource>,
+        mode: PlaybackMode,
+        spatial: bool,
+        mixer: &AudioMixer,
+    ) -> impl Bundle {
+        (
+            AudioPlayer::new(source),
+            PlaybackSettings {
+                mode,
+                spatial,
+                volume: Volume::new(mixer.volume(self.bus) * self.volume),
+                ..PlaybackSettings::ONCE
+            },
+            self,
+        )
+    }
+}
+
+/// Footstep sounds for every surface
+#[derive(Resource, Default)]
+pub struct FootstepSounds(pub HashMap<SurfaceType, Vec<Handle<AudioSource>>>);
+
+/// Player's breathing, it's louder when stamina is low
+#[derive(Component)]
+pub struct BreathingSound;
+
+/// Player's heartbeat, it's louder and faster when fear is high
+#[derive(Component)]
+pub struct HeartbeatSound;
+
+/// Zone of ambience sound, it's authored in blender as object
+/// with this component. The size of zone is `half_extents * scale`.
+#[derive(Component, Clone, Debug, Educe, Reflect)]
+#[reflect(Component)]
+#[educe(Default)]
+#[require(Transform)]
+pub struct AmbienceZone {
+    /// path to looped sound (for example "audio/ambience/forest_wind.ogg")
+    pub sound: String,
+
+    #[educe(Default = Vec3::ONE)] /// half size of zone's box
+    pub half_extents: Vec3,
+
+    #[educe(Default = 3.0)] /// distance of fade outside of zone's box
+    pub fade_distance: f32,
+
+    #[educe(Default = 1.0)] /// volume of sound inside zone
+    pub volume: f32,
+}
+
+impl AmbienceZone {
+    /// Return weight of zone in point: 1.0 - inside, 0.0 - far outside
+    pub fn weight(&self, zone_transform: &GlobalTransform, point: Vec3) -> f32 {
+        box_zone_weight(self.half_extents, self.fade_distance, zone_transform, point)
+    }
+}
+
+/// Looped sound of ambience zone
+#[derive(Component)]
+pub struct AmbienceSound;
+
+/// Speed of ambience cross-fade (volume per second)
+pub(super) const AMBIENCE_FADE_SPEED: f32 = 0.5;
+
+/// Music director - layers music stems by tension
+#[derive(Resource, Clone, Debug, Educe, Reflect)]
+#[reflect(Resource)]
+#[educe(Default)]
+pub struct MusicDirector {
+    #[educe(Default = false)] /// monster chases the player
+    pub chase: bool,
+
+    #[educe(Default = 15.0)] /// monster closer than this distance hears and chases the player (scaled by weather)
+    pub chase_distance: f32,
+
+    #[educe(Default = "audio/music/stingers/chase.ogg".into())] /// stinger played when chase starts
+    pub chase_stinger: String,
+
+    #[educe(Default = 0.0)] /// current (smoothed) tension, from 0.0 to 1.0
+    pub tension: f32,
+
+    #[educe(Default = 0.25)] /// speed of tension change (per second)
+    pub transition_speed: f32,
+
+    #[educe(Default = 100.0)] /// fear at which tension is maximal (without chase)
+    pub max_fear: f32,
+
+    #[educe(Default = vec![
+        ("audio/music/drone.ogg".into(), 0.0),
+        ("audio/music/pulse.ogg".into(), 0.3),
+        ("audio/music/strings.ogg".into(), 0.6),
+        ("audio/music/chase.ogg".into(), 0.9),
+    ])]
+    /// stems of music: path and tension at which stem starts play
+    pub stems: Vec<(String, f32)>,
+}
+
+impl MusicDirector {
+    /// Return tension which director wants
+    pub fn target_tension(&self, fear: f32) -> f32 {
+        if self.chase {
+            return 1.0;
+        }
+
+        // without chase music can't reach maximal tension
+        (fear / self.max_fear).clamp(0.0, 1.0) * 0.85
+    }
+}
+
+/// One layer of music
+#[derive(Component)]
+pub struct MusicStem {
+    /// tension at which this stem starts play
+    pub threshold: f32,
+}
+
+/// Width of tension range in which stem fades in
+pub(super) const STEM_FADE_RANGE: f32 = 0.15;
+
+/// Event for play stinger (short music phrase) on scripted event,
+/// it's also sent by triggers with action "stinger:<path>"
+#[derive(Event, Clone, Debug)]
+pub struct PlayStinger(pub String);
@@ -0,0 +1,293 @@
+//! implementation of audio systems
+
+// import crates
+use bevy::{audio::PlaybackMode, prelude::*};
+
+// import data from this crate
+use crate::components::{Monster, SurfaceType};
+use crate::player::structures::{PlayerComponent, PlayerControllerData, MAX_STAMINA};
+use crate::triggers::structures::TriggerEntered;
+use crate::weather::structures::WeatherEffects;
+use super::structures::*;
+
+/// load footstep sounds for all surfaces
+pub fn load_footsteps(
+    mut commands: Commands,
+    assets: Res<AssetServer>,
+) {
+    let mut sounds = FootstepSounds::default();
+
+    for surface in SurfaceType::ALL {
+        let variations = (1..=FOOTSTEP_VARIATIONS)
+            .filter_map(|i| assets.load_sound(format!("audio/footsteps/{}_{i}.ogg", surface.name())))
+            .collect();
+
+        sounds.0.insert(surface, variations);
+    }
+
+    commands.insert_resource(sounds);
+}
+
+/// add breathing and heartbeat to new player
+pub fn setup_body_sounds(
+    mut commands: Commands,
+    player_query: Query<Entity, Added<PlayerComponent>>,
+    mixer: Res<AudioMixer>,
+    assets: Res<AssetServer>,
+) {
+    for player in player_query.iter() {
+        commands.entity(player).with_children(|parent| {
+            parent.spawn((
+                #[cfg(debug_assertions)] Name::new("Breathing"),
+                AudioEmitter::new(MixerBus::Voice, 0.0).sound(
+                    assets.load_sound("audio/player/breathing.ogg").unwrap_or_default(),
+                    PlaybackMode::Loop, false, &mixer
+                ),
+                BreathingSound,
+            ));
+
+            parent.spawn((
+                #[cfg(debug_assertions)] Name::new("Heartbeat"),
+                AudioEmitter::new(MixerBus::Voice, 0.0).sound(
+                    assets.load_sound("audio/player/heartbeat.ogg").unwrap_or_default(),
+                    PlaybackMode::Loop, false, &mixer
+                ),
+                HeartbeatSound,
+            ));
+        });
+    }
+}
+
+/// play step sound when player travelled stride distance
+pub fn play_footsteps(
+    mut commands: Commands,
+    mut player_query: Query<(&Transform, &mut PlayerControllerData)>,
+    sounds: Res<FootstepSounds>,
+    mixer: Res<AudioMixer>,
+    mut variation: Local<usize>,
+) {
+    let Ok((player_transform, mut player_controller)) = player_query.get_single_mut() else { return; };
+
+    if player_controller.travelled < FOOTSTEP_STRIDE {
+        return;
+    }
+    player_controller.travelled -= FOOTSTEP_STRIDE;
+
+    // steps are heard only on ground
+    if player_controller.ground.is_none() {
+        return;
+    }
+
+    let Some(variations) = sounds.0.get(&player_controller.surface) else { return; };
+    if variations.is_empty() {
+        return;
+    }
+
+    // step with next variation
+    *variation = (*variation + 1) % variations.len();
+
+    // quiet steps while crouch
+    let volume = if player_controller.crouched { 0.35 } else { 1.0 };
+
+    commands.spawn((
+        #[cfg(debug_assertions)] Name::new("Footstep"),
+        Transform::from_translation(player_transform.translation),
+        AudioEmitter::new(MixerBus::Sfx, volume).sound(
+            variations[*variation].clone(), PlaybackMode::Despawn, true, &mixer
+        ),
+    ));
+}
+
+/// update breathing by stamina and heartbeat by fear
+pub fn update_body_sounds(
+    mut breathing_query: Query<&mut AudioEmitter, (With<BreathingSound>, Without<HeartbeatSound>)>,
+    mut heartbeat_query: Query<
+        (&mut AudioEmitter, Option<&AudioSink>), (With<HeartbeatSound>, Without<BreathingSound>)
+    >,
+    player_query: Query<&PlayerComponent>,
+) {
+    let Ok(player) = player_query.get_single() else { return; };
+
+    // the less stamina, the louder breathing
+    let tiredness = 1.0 - (player.stamina / MAX_STAMINA).clamp(0.0, 1.0);
+    for mut emitter in breathing_query.iter_mut() {
+        emitter.volume = tiredness;
+    }
+
+    // the more fear, the louder and faster heartbeat
+    let fear = (player.fear / 100.0).clamp(0.0, 1.0);
+    for (mut emitter, sink) in heartbeat_query.iter_mut() {
+        emitter.volume = fear;
+
+        if let Some(sink) = sink {
+            sink.set_speed(1.0 + fear);
+        }
+    }
+}
+
+/// apply mixer's volumes to all emitters
+pub fn apply_mixer(
+    sinks_query: Query<(&AudioEmitter, &AudioSink)>,
+    spatial_sinks_query: Query<(&AudioEmitter, &SpatialAudioSink)>,
+    mixer: Res<AudioMixer>,
+) {
+    for (emitter, sink) in sinks_query.iter() {
+        sink.set_volume(mixer.volume(emitter.bus) * emitter.volume);
+    }
+
+    for (emitter, sink) in spatial_sinks_query.iter() {
+        sink.set_volume(mixer.volume(emitter.bus) * emitter.volume);
+    }
+}
+
+/// add looped sound to new ambience zones
+pub fn setup_ambience_zones(
+    mut commands: Commands,
+    zones_query: Query<(Entity, &AmbienceZone), Added<AmbienceZone>>,
+    mixer: Res<AudioMixer>,
+    assets: Res<AssetServer>,
+) {
+    for (entity, zone) in zones_query.iter() {
+        commands.entity(entity).with_children(|parent| {
+            parent.spawn((
+                #[cfg(debug_assertions)] Name::new("Ambience"),
+                AudioEmitter::new(MixerBus::Ambience, 0.0).sound(
+                    assets.load_sound(zone.sound.clone()).unwrap_or_default(),
+                    PlaybackMode::Loop, false, &mixer
+                ),
+                AmbienceSound,
+            ));
+        });
+    }
+}
+
+/// cross-fade ambience sounds while player moves between zones
+pub fn update_ambience(
+    mut sounds_query: Query<(&Parent, &mut AudioEmitter), With<AmbienceSound>>,
+    zones_query: Query<(&AmbienceZone, &GlobalTransform)>,
+    player_query: Query<&GlobalTransform, With<PlayerComponent>>,
+    time: Res<Time>,
+) {
+    let player_position = player_query.get_single()
+        .map(|transform| transform.translation())
+        .ok();
+
+    let step = AMBIENCE_FADE_SPEED * time.delta_secs();
+    for (parent, mut emitter) in sounds_query.iter_mut() {
+        let Ok((zone, zone_transform)) = zones_query.get(parent.get()) else { continue; };
+
+        // without player all ambience fades out
+        let target = match player_position {
+            Some(position) => zone.weight(zone_transform, position) * zone.volume,
+            None => 0.0,
+        };
+
+        emitter.volume = move_towards(emitter.volume, target, step);
+    }
+}
+
+/// spawn all music stems, they play synchronously
+pub fn setup_music(
+    mut commands: Commands,
+    director: Res<MusicDirector>,
+    mixer: Res<AudioMixer>,
+    assets: Res<AssetServer>,
+) {
+    for (path, threshold) in director.stems.iter() {
+        commands.spawn((
+            #[cfg(debug_assertions)] Name::new(format!("Music stem {path}")),
+            AudioEmitter::new(MixerBus::Music, 0.0).sound(
+                assets.load_sound(path.clone()).unwrap_or_default(),
+                PlaybackMode::Loop, false, &mixer
+            ),
+            MusicStem { threshold: *threshold },
+        ));
+    }
+}
+
+/// start chase when monster is near the player
+pub fn update_chase(
+    mut director: ResMut<MusicDirector>,
+    mut stingers: EventWriter<PlayStinger>,
+    monsters_query: Query<&GlobalTransform, With<Monster>>,
+    player_query: Query<&GlobalTransform, With<PlayerComponent>>,
+    weather: Res<WeatherEffects>,
+) {
+    let Ok(player_transform) = player_query.get_single() else { return; };
+
+    // rain and storm hide player's sounds, so monster hears player closer
+    let distance = director.chase_distance * weather.hearing_multiplier;
+    let chase = monsters_query.iter().any(|monster_transform| {
+        monster_transform.translation().distance(player_transform.translation()) < distance
+    });
+
+    if chase && !director.chase {
+        stingers.send(PlayStinger(director.chase_stinger.clone()));
+    }
+
+    director.chase = chase;
+}
+
+/// update tension and volumes of music stems
+pub fn update_music(
+    mut stems_query: Query<(&MusicStem, &mut AudioEmitter)>,
+    mut director: ResMut<MusicDirector>,
+    player_query: Query<&PlayerComponent>,
+    time: Res<Time>,
+) {
+    let fear = player_query.get_single().map(|player| player.fear).unwrap_or(0.0);
+
+    // smooth transition of tension
+    let target = director.target_tension(fear);
+    let step = director.transition_speed * time.delta_secs();
+    director.tension = move_towards(director.tension, target, step);
+
+    for (stem, mut emitter) in stems_query.iter_mut() {
+        let volume = ((director.tension - stem.threshold) / STEM_FADE_RANGE).clamp(0.0, 1.0);
+
+        // first stem always plays
+        emitter.volume = if stem.threshold <= 0.0 { 1.0 } else { volume };
+    }
+}
+
+/// play stingers of triggers with action "stinger:<path>"
+pub fn play_triggered_stingers(
+    mut entered: EventReader<TriggerEntered>,
+    mut stingers: EventWriter<PlayStinger>,
+) {
+    for event in entered.read() {
+        if let Some(path) = event.argument("stinger") {
+            stingers.send(PlayStinger(path.to_string()));
+        }
+    }
+}
+
+/// play stingers on scripted events
+pub fn play_stingers(
+    mut commands: Commands,
+    mut stingers: EventReader<PlayStinger>,
+    mixer: Res<AudioMixer>,
+    assets: Res<AssetServer>,
+) {
+    for stinger in stingers.read() {
+        let Some(sound) = assets.load_sound(stinger.0.clone()) else { continue; };
+
+        commands.spawn((
+            #[cfg(debug_assertions)] Name::new("Stinger"),
+            AudioEmitter::new(MixerBus::Music, 1.0).sound(
+                sound, PlaybackMode::Despawn, false, &mixer
+            ),
+        ));
+    }
+}
+
+// helper functions
+
+/// Move value to target, but not more than step
+#[inline] fn move_towards(value: f32, target: f32, step: f32) -> f32 {
+    if (target - value).abs() <= step {
+        target
+    } else {
+        value + step * (target - value).signum()
+    }
+}
@@ -2,16 +2,679 @@
 
 // import crates
 use bevy::prelude::*;
+use educe::Educe;
 
-/// main camera in game
+use crate::player::structures::CAMERA_WALK_TRANSLATION;
+
+/// main camera in game, it's used by third-person
+/// and cinematic modes (first-person camera is attached to player)
 #[derive(Component)]
 #[require(Transform, Camera3d)]
 pub struct CameraComponent {}
 
+/// Mode of game's camera
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CameraMode {
+    /// camera of player's eyes
+    #[default]
+    FirstPerson,
+    /// camera behind player on spring arm
+    ThirdPerson,
+    /// camera follows path of entity with `CinematicPath`
+    Cinematic(Entity),
+}
+
+/// Event: change camera mode with blending
+#[derive(Event, Clone, Copy, Debug)]
+pub struct SetCameraMode(pub CameraMode);
+
+/// Blend between camera modes
+#[derive(Resource, Debug, Educe)]
+#[educe(Default)]
+pub struct CameraBlend {
+    #[educe(Default = Transform::IDENTITY)] /// transform of camera when blend started
+    pub from: Transform,
+
+    #[educe(Default = CAMERA_BLEND_TIME)] /// time from blend start (in seconds)
+    pub elapsed: f32,
+
+    #[educe(Default = CAMERA_BLEND_TIME)] /// duration of blend (in seconds)
+    pub duration: f32,
+}
+
+impl CameraBlend {
+    /// Return blend factor from 0.0 to 1.0 (smoothed)
+    pub fn factor(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+
+        let t = (self.elapsed / self.duration).clamp(0.0, 1.0);
+        t * t * (3.0 - 2.0 * t)
+    }
+
+    /// Return true if blend is finished
+    pub fn finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+}
+
+/// Settings of third-person spring arm
+#[derive(Resource, Debug, Educe)]
+#[educe(Default)]
+pub struct ThirdPersonSettings {
+    #[educe(Default = 3.5)] /// max length of arm
+    pub distance: f32,
+
+    #[educe(Default = 0.5)] /// sideways offset of arm's origin
+    pub shoulder: f32,
+
+    #[educe(Default = 0.2)] /// distance from camera to wall
+    pub margin: f32,
+
+    #[educe(Default = 3.0)] /// speed of arm extension (shortening is instant)
+    pub extend_speed: f32,
+
+    #[educe(Default = 3.5)] /// current length of arm
+    pub current: f32,
+}
+
+/// What cinematic camera looks at
+#[derive(Clone, Copy, Debug)]
+pub enum CinematicTarget {
+    /// look at entity
+    Entity(Entity),
+    /// look at point
+    Point(Vec3),
+    /// look along path
+    Forward,
+}
+
+/// Spline path of cinematic camera (catmull-rom through points)
+#[derive(Component, Clone, Debug)]
+pub struct CinematicPath {
+    /// points of path
+    pub points: Vec<Vec3>,
+    /// duration of path (in seconds)
+    pub duration: f32,
+    /// target of camera
+    pub look_at: CinematicTarget,
+    /// time from start of path (in seconds)
+    pub elapsed: f32,
+    /// camera advances path itself, if false someone else sets `elapsed`
+    pub auto_advance: bool,
+}
+
+impl CinematicPath {
+    /// Create path through points
+    pub fn new(points: Vec<Vec3>, duration: f32, look_at: CinematicTarget) -> Self {
+        Self { points, duration, look_at, elapsed: 0.0, auto_advance: true }
+    }
+
+    /// Return progress of path, from 0.0 to 1.0
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+
+        (self.elapsed / self.duration).clamp(0.0, 1.0)
+    }
+
+    /// Return true if camera is at the end of path
+    pub fn finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// Return position on path, t is from 0.0 to 1.0
+    pub fn position(&self, t: f32) -> Vec3 {
+        match self.points.len() {
+            0 => Vec3::ZERO,
+            1 => self.points[0],
+            count => {
+                let segments = count - 1;
+                let scaled = t.clamp(0.0, 1.0) * segments as f32;
+                let segment = (scaled.floor() as usize).min(segments - 1);
+                let local = scaled - segment as f32;
+
+                // neighbours of segment, ends are duplicated
+                let p0 = self.points[segment.saturating_sub(1)];
+                let p1 = self.points[segment];
+                let p2 = self.points[segment + 1];
+                let p3 = self.points[(segment + 2).min(count - 1)];
+
+                catmull_rom(p0, p1, p2, p3, local)
+            },
+        }
+    }
+}
+
+/// Debug free-fly camera, it's detached from player
+/// and flies through geometry
+#[derive(Component)]
+#[require(Transform, Camera3d)]
+pub struct DebugFlyCamera {
+    /// speed of camera (meters per second)
+    pub speed: f32,
+    /// rotation of camera (pitch, yaw)
+    pub rotation: Vec2,
+}
+
+/// State of debug free-fly camera
+#[derive(Resource, Default)]
+pub struct FlyCameraState {
+    /// show rapier's debug view while camera is active
+    pub debug_render: bool,
+    /// player's input was enabled before camera activated
+    pub input_was_enabled: bool,
+}
+
+/// Stack of camera effects: head bob, landing dip,
+/// lean, smooth crouch and shake
+#[derive(Component, Debug, Educe)]
+#[educe(Default)]
+pub struct CameraEffects {
+    #[educe(Default = 0.0)] /// phase of head bob
+    pub bob_phase: f32,
+
+    #[educe(Default = 0.0)] /// current strength of head bob
+    pub bob_amount: f32,
+
+    #[educe(Default = 0.0)] /// vertical offset of landing dip
+    pub landing_offset: f32,
+
+    #[educe(Default = 0.0)] /// velocity of landing dip spring
+    pub landing_velocity: f32,
+
+    #[educe(Default = 0.0)] /// current lean, from -1.0 (left) to 1.0 (right)
+    pub lean: f32,
+
+    #[educe(Default = CAMERA_WALK_TRANSLATION.y)] /// current height of camera
+    pub height: f32,
+
+    #[educe(Default = 0.0)] /// trauma of shake, from 0.0 to 1.0
+    pub trauma: f32,
+
+    #[educe(Default = None)] /// player's position in last frame
+    pub last_position: Option<Vec3>,
+}
+
+/// Event: add trauma to camera (explosions, monster's scream, etc.)
+#[derive(Event, Clone, Copy, Debug)]
+pub struct AddCameraTrauma(pub f32);
+
+// const's of camera effects
+pub(crate) const BOB_AMPLITUDE: f32 = 0.045;
+pub(crate) const BOB_FREQUENCY: f32 = 3.6; // radians per meter
+pub(crate) const BOB_REFERENCE_SPEED: f32 = 2.0;
+pub(crate) const LANDING_DIP_SCALE: f32 = 0.12;
+pub(crate) const LANDING_MAX_DIP_SPEED: f32 = 2.5;
+pub(crate) const LANDING_SPRING: f32 = 120.0;
+pub(crate) const HARD_LANDING_SPEED: f32 = 8.0;
+pub(crate) const LEAN_DISTANCE: f32 = 0.45;
+pub(crate) const LEAN_ANGLE: f32 = 0.2;
+pub(crate) const LEAN_MARGIN: f32 = 0.25;
+pub(crate) const LEAN_SMOOTHNESS: f32 = 8.0;
+pub(crate) const CROUCH_SMOOTHNESS: f32 = 10.0;
+pub(crate) const TRAUMA_DECAY: f32 = 0.8;
+pub(crate) const MAX_SHAKE_ANGLE: f32 = 0.08;
+
+// const's of camera modes
+pub(crate) const CAMERA_BLEND_TIME: f32 = 0.6;
+
+// const's of debug camera
+pub(crate) const FLY_CAMERA_SPEED: f32 = 8.0;
+pub(crate) const FLY_CAMERA_MIN_SPEED: f32 = 0.5;
+pub(crate) const FLY_CAMERA_MAX_SPEED: f32 = 100.0;
+
+pub struct CameraPlugin;
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<AddCameraTrauma>();
+        app.add_event::<SetCameraMode>();
+        app.insert_resource(CameraMode::default());
+        app.insert_resource(CameraBlend::default());
+        app.insert_resource(ThirdPersonSettings::default());
+
+        app.add_systems(Startup, camera_systems::setup_camera);
+        app.add_systems(Update, (
+            camera_systems::update_camera_effects,
+            camera_systems::switch_camera_mode,
+            camera_systems::update_camera_mode,
+        ).chain().after(crate::player::systems::move_character));
+
+        #[cfg(debug_assertions)] {
+            app.insert_resource(FlyCameraState { debug_render: true, ..default() });
+            app.add_systems(Update, (
+                camera_systems::toggle_fly_camera,
+                camera_systems::move_fly_camera,
+            ).chain()
+                .in_set(crate::components::HotkeySystems)
+                .after(crate::player::systems::update_cursor_visible)
+                .before(crate::player::systems::update_input));
+        }
+    }
+}
+
 pub mod camera_systems {
     //! implementation of camera systems
     // import crates
-    // use bevy::prelude::*;
+    use std::f32::consts::{FRAC_PI_2, TAU};
+
+    use bevy::{input::mouse::{AccumulatedMouseMotion, AccumulatedMouseScroll}, prelude::*, time::Real};
+    use bevy_rapier3d::{prelude::*, render::DebugRenderContext};
 
+    // import data from this crate
+    use crate::player::structures::{
+        PlayerCameraPivot, PlayerControllerData, PlayerInputEnabled, PlayerLanded, PlayersInput,
+        CAMERA_CROUCH_TRANSLATION,
+    };
+    use crate::settings::structures::{GameSettings, MOUSE_SENSITIVITY_SCALE};
+    use super::*;
+
+    /// apply camera effects to player's camera
+    pub fn update_camera_effects(
+        mut pivot_query: Query<(&mut Transform, &mut CameraEffects), With<PlayerCameraPivot>>,
+        player_query: Query<(Entity, &Transform, &PlayerControllerData), Without<PlayerCameraPivot>>,
+        rapier_context: Query<(&RapierContextColliders, &RapierQueryPipeline, &RapierRigidBodySet)>,
+        mut trauma_events: EventReader<AddCameraTrauma>,
+        mut landed: EventReader<PlayerLanded>,
+        input: Res<PlayersInput>,
+        time: Res<Time>,
+    ) {
+        let Ok((mut pivot_transform, mut effects)) = pivot_query.get_single_mut() else { return; };
+        let Ok((player_entity, player_transform, controller)) = player_query.get_single() else { return; };
+
+        let dt = time.delta_secs();
+        if dt <= 0.0 {
+            return;
+        }
+
+        // get horizontal speed of player
+        let position = player_transform.translation;
+        let speed = effects.last_position
+            .map(|last| (position - last).xz().length() / dt)
+            .unwrap_or(0.0);
+        effects.last_position = Some(position);
+
+        // head bob depends on speed
+        let bob_target = if controller.grounded && !controller.noclip {
+            (speed / BOB_REFERENCE_SPEED).min(1.5)
+        } else {
+            0.0
+        };
+        effects.bob_amount = smooth(effects.bob_amount, bob_target, 6.0, dt);
+        effects.bob_phase = (effects.bob_phase + speed * BOB_FREQUENCY * dt) % TAU;
+
+        let bob = Vec2::new(
+            effects.bob_phase.cos() * BOB_AMPLITUDE * 0.5,
+            (effects.bob_phase * 2.0).sin() * BOB_AMPLITUDE,
+        ) * effects.bob_amount;
+
+        // landing dip, it's scaled by fall velocity
+        for event in landed.read() {
+            effects.landing_velocity -= (event.fall_speed * LANDING_DIP_SCALE).min(LANDING_MAX_DIP_SPEED);
+
+            if event.fall_speed > HARD_LANDING_SPEED {
+                effects.trauma += (event.fall_speed - HARD_LANDING_SPEED) * 0.05;
+            }
+        }
+
+        // critically damped spring returns camera back
+        let damping = 2.0 * LANDING_SPRING.sqrt();
+        let acceleration = -LANDING_SPRING * effects.landing_offset - damping * effects.landing_velocity;
+        effects.landing_velocity += acceleration * dt;
+        effects.landing_offset += effects.landing_velocity * dt;
+
+        // smooth crouch
+        let height_target = if controller.crouched {
+            CAMERA_CROUCH_TRANSLATION.y
+        } else {
+            CAMERA_WALK_TRANSLATION.y
+        };
+        effects.height = smooth(effects.height, height_target, CROUCH_SMOOTHNESS, dt);
+
+        // lean, but not into walls
+        let mut lean_target = (input.lean_right as i32 - input.lean_left as i32) as f32;
+        if lean_target != 0.0 {
+            if let Ok((colliders, query_pipeline, rigidbody_set)) = rapier_context.get_single() {
+                let origin = position + Vec3::Y * effects.height;
+                let direction = *player_transform.right() * lean_target;
+
+                if let Some((_, distance)) = query_pipeline.cast_ray(
+                    colliders, rigidbody_set, origin, direction,
+                    LEAN_DISTANCE + LEAN_MARGIN, true,
+                    QueryFilter::new().exclude_collider(player_entity).exclude_sensors()
+                ) {
+                    lean_target *= ((distance - LEAN_MARGIN) / LEAN_DISTANCE).clamp(0.0, 1.0);
+                }
+            }
+        }
+        effects.lean = smooth(effects.lean, lean_target, LEAN_SMOOTHNESS, dt);
+
+        // trauma-based shake
+        for AddCameraTrauma(trauma) in trauma_events.read() {
+            effects.trauma += trauma;
+        }
+        effects.trauma = (effects.trauma - TRAUMA_DECAY * dt).clamp(0.0, 1.0);
+
+        let shake = effects.trauma * effects.trauma * MAX_SHAKE_ANGLE;
+        let elapsed = time.elapsed_secs();
+        let shake_rotation = Quat::from_euler(
+            EulerRot::XYZ,
+            (elapsed * 31.0).sin() * shake,
+            (elapsed * 23.0 + 1.3).sin() * shake,
+            (elapsed * 17.0 + 2.7).sin() * shake,
+        );
+
+        // apply all effects
+        pivot_transform.translation = Vec3::new(
+            effects.lean * LEAN_DISTANCE + bob.x,
+            effects.height + bob.y + effects.landing_offset,
+            0.0,
+        );
+        pivot_transform.rotation =
+            Quat::from_euler(EulerRot::XYZ, controller.rotation.x, 0.0, -effects.lean * LEAN_ANGLE)
+            * shake_rotation;
+    }
+
+    /// spawn inactive world camera
+    pub fn setup_camera(
+        mut commands: Commands,
+        settings: Res<GameSettings>,
+    ) {
+        commands.spawn((
+            #[cfg(debug_assertions)] Name::new("World camera"),
+            Camera { is_active: false, ..default() },
+            Projection::from(PerspectiveProjection { fov: settings.fov.to_radians(), ..default() }),
+            CameraComponent {},
+        ));
+    }
+
+    /// switch between first and third person by V, or by event
+    pub fn switch_camera_mode(
+        mut mode_events: EventReader<SetCameraMode>,
+        mut blend: ResMut<CameraBlend>,
+        mut mode: ResMut<CameraMode>,
+        world_camera_query: Query<&Transform, With<CameraComponent>>,
+        pivot_query: Query<&GlobalTransform, With<PlayerCameraPivot>>,
+        input_enabled: Res<PlayerInputEnabled>,
+        keys: Res<ButtonInput<KeyCode>>,
+    ) {
+        let mut new_mode = *mode;
+
+        if input_enabled.0 && keys.just_pressed(KeyCode::KeyV) {
+            new_mode = match *mode {
+                CameraMode::FirstPerson => CameraMode::ThirdPerson,
+                CameraMode::ThirdPerson => CameraMode::FirstPerson,
+                cinematic => cinematic,
+            };
+        }
+
+        for SetCameraMode(event_mode) in mode_events.read() {
+            new_mode = *event_mode;
+        }
+
+        if new_mode == *mode {
+            return;
+        }
+
+        // start blend from camera which is visible now
+        let from = match (*mode, blend.finished()) {
+            (CameraMode::FirstPerson, true) => pivot_query.get_single().ok().map(|transform| transform.compute_transform()),
+            _ => world_camera_query.get_single().ok().copied(),
+        };
+
+        if let Some(from) = from {
+            blend.from = from;
+            blend.elapsed = 0.0;
+        }
+
+        *mode = new_mode;
+    }
+
+    /// move world camera and choose active camera
+    pub fn update_camera_mode(
+        mut commands: Commands,
+        mut world_camera_query: Query<
+            (Entity, &mut Transform, &mut Camera, Has<IsDefaultUiCamera>),
+            (With<CameraComponent>, Without<PlayerCameraPivot>)
+        >,
+        mut pivot_query: Query<
+            (Entity, &GlobalTransform, &mut Camera, Has<IsDefaultUiCamera>),
+            (With<PlayerCameraPivot>, Without<CameraComponent>)
+        >,
+        mut paths_query: Query<(&mut CinematicPath, &GlobalTransform)>,
+        mut third_person: ResMut<ThirdPersonSettings>,
+        mut blend: ResMut<CameraBlend>,
+        player_query: Query<(Entity, &Transform, &PlayerControllerData), Without<CameraComponent>>,
+        targets_query: Query<&GlobalTransform, Without<CinematicPath>>,
+        rapier_context: Query<(&RapierContextColliders, &RapierQueryPipeline, &RapierRigidBodySet)>,
+        fly_camera_query: Query<(), With<DebugFlyCamera>>,
+        mode: Res<CameraMode>,
+        time: Res<Time>,
+    ) {
+        let Ok((world_entity, mut world_transform, mut world_camera, world_is_ui)) =
+            world_camera_query.get_single_mut() else { return; };
+
+        let dt = time.delta_secs();
+        blend.elapsed += dt;
+
+        // get target of camera in current mode
+        let target: Option<Transform> = match *mode {
+            CameraMode::FirstPerson => pivot_query.get_single()
+                .ok()
+                .map(|(_, transform, _, _)| transform.compute_transform()),
+            CameraMode::ThirdPerson => player_query.get_single().ok().map(|(entity, transform, controller)| {
+                let rotation = Quat::from_euler(EulerRot::YXZ, controller.rotation.y, controller.rotation.x, 0.0);
+                let origin = transform.translation
+                    + Vec3::Y * CAMERA_WALK_TRANSLATION.y
+                    + rotation * Vec3::X * third_person.shoulder;
+                let back = rotation * Vec3::Z;
+
+                // spring arm doesn't go through walls
+                let mut length = third_person.distance;
+                if let Ok((colliders, query_pipeline, rigidbody_set)) = rapier_context.get_single() {
+                    if let Some((_, distance)) = query_pipeline.cast_ray(
+                        colliders, rigidbody_set, origin, back,
+                        third_person.distance + third_person.margin, true,
+                        QueryFilter::new().exclude_collider(entity).exclude_sensors()
+                    ) {
+                        length = (distance - third_person.margin).max(0.0);
+                    }
+                }
+
+                third_person.current = if length < third_person.current {
+                    length
+                } else {
+                    (third_person.current + third_person.extend_speed * dt).min(length)
+                };
+
+                Transform::from_translation(origin + back * third_person.current).with_rotation(rotation)
+            }),
+            CameraMode::Cinematic(entity) => paths_query.get_mut(entity).ok().map(|(mut path, path_transform)| {
+                if path.auto_advance {
+                    path.elapsed += dt;
+                }
+
+                let t = path.progress();
+                let position = path_transform.transform_point(path.position(t));
+                let look_at = match path.look_at {
+                    CinematicTarget::Entity(target) => targets_query.get(target)
+                        .map(|transform| transform.translation())
+                        .unwrap_or(position + Vec3::NEG_Z),
+                    CinematicTarget::Point(point) => point,
+                    CinematicTarget::Forward => {
+                        let ahead = path_transform.transform_point(path.position((t + 0.01).min(1.0)));
+                        if ahead.distance_squared(position) > f32::EPSILON { ahead } else { position + Vec3::NEG_Z }
+                    },
+                };
+
+                Transform::from_translation(position).looking_at(look_at, Vec3::Y)
+            }),
+        };
+
+        // blend from previous camera
+        if let Some(target) = target {
+            let factor = blend.factor();
+            world_transform.translation = blend.from.translation.lerp(target.translation, factor);
+            world_transform.rotation = blend.from.rotation.slerp(target.rotation, factor);
+        }
+
+        // choose active camera, debug fly camera overrides both
+        let flying = !fly_camera_query.is_empty();
+        let first_person = *mode == CameraMode::FirstPerson && blend.finished();
+
+        world_camera.is_active = !first_person && !flying;
+        if world_camera.is_active != world_is_ui {
+            if world_camera.is_active {
+                commands.entity(world_entity).insert(IsDefaultUiCamera);
+            } else {
+                commands.entity(world_entity).remove::<IsDefaultUiCamera>();
+            }
+        }
+
+        if let Ok((pivot_entity, _, mut pivot_camera, pivot_is_ui)) = pivot_query.get_single_mut() {
+            pivot_camera.is_active = first_person && !flying;
+            if pivot_camera.is_active != pivot_is_ui {
+                if pivot_camera.is_active {
+                    commands.entity(pivot_entity).insert(IsDefaultUiCamera);
+                } else {
+                    commands.entity(pivot_entity).remove::<IsDefaultUiCamera>();
+                }
+            }
+        }
+    }
+
+    /// detach camera by F2, exit by F2 (or by T with teleport of player),
+    /// switch rapier's debug view by F4 while camera is active
+    pub fn toggle_fly_camera(
+        mut commands: Commands,
+        pivot_query: Query<(&GlobalTransform, &Projection), With<PlayerCameraPivot>>,
+        mut player_query: Query<(&mut Transform, &mut PlayerControllerData), Without<DebugFlyCamera>>,
+        mut debug_render: Option<ResMut<DebugRenderContext>>,
+        mut input_enabled: ResMut<PlayerInputEnabled>,
+        mut state: ResMut<FlyCameraState>,
+        fly_query: Query<(Entity, &Transform), With<DebugFlyCamera>>,
+        keys: Res<ButtonInput<KeyCode>>,
+    ) {
+        let Ok((pivot_transform, projection)) = pivot_query.get_single() else { return; };
+
+        match fly_query.get_single() {
+            // activate camera at player's camera
+            Err(_) if keys.just_pressed(KeyCode::F2) => {
+                let (_, rotation, _) = pivot_transform.to_scale_rotation_translation();
+                let (yaw, pitch, _) = rotation.to_euler(EulerRot::YXZ);
+
+                commands.spawn((
+                    Name::new("Debug fly camera"),
+                    Transform::from_translation(pivot_transform.translation()).with_rotation(rotation),
+                    projection.clone(),
+                    IsDefaultUiCamera,
+                    DebugFlyCamera { speed: FLY_CAMERA_SPEED, rotation: Vec2::new(pitch, yaw) },
+                ));
+
+                state.input_was_enabled = input_enabled.0;
+                input_enabled.0 = false;
+
+                if let Some(debug_render) = debug_render.as_mut() {
+                    debug_render.enabled = state.debug_render;
+                }
+            },
+            // return to player
+            Ok((entity, fly_transform)) if keys.any_just_pressed([KeyCode::F2, KeyCode::KeyT]) => {
+                if keys.just_pressed(KeyCode::KeyT) {
+                    if let Ok((mut player_transform, mut controller)) = player_query.get_single_mut() {
+                        player_transform.translation = fly_transform.translation;
+                        controller.velocity = Vec3::ZERO;
+                    }
+                }
+
+                commands.entity(entity).despawn_recursive();
+                input_enabled.0 = state.input_was_enabled;
+
+                if let Some(debug_render) = debug_render.as_mut() {
+                    debug_render.enabled = false;
+                }
+            },
+            Ok(_) => {
+                if keys.just_pressed(KeyCode::F4) {
+                    state.debug_render = !state.debug_render;
+
+                    if let Some(debug_render) = debug_render.as_mut() {
+                        debug_render.enabled = state.debug_render;
+                    }
+                }
+
+                // right click only grabs cursor, player stays still while camera flies
+                if input_enabled.0 {
+                    state.input_was_enabled = true;
+                    input_enabled.0 = false;
+                }
+            },
+            _ => {},
+        }
+    }
+
+    /// fly by WASD, Space and Ctrl, change speed by mouse wheel
+    pub fn move_fly_camera(
+        mut camera_query: Query<(&mut Transform, &mut DebugFlyCamera)>,
+        mouse_motion: Res<AccumulatedMouseMotion>,
+        mouse_scroll: Res<AccumulatedMouseScroll>,
+        keys: Res<ButtonInput<KeyCode>>,
+        settings: Res<GameSettings>,
+        time: Res<Time<Real>>,
+    ) {
+        let Ok((mut transform, mut camera)) = camera_query.get_single_mut() else { return; };
+
+        // update speed
+        if mouse_scroll.delta.y != 0.0 {
+            camera.speed = (camera.speed * 1.2_f32.powf(mouse_scroll.delta.y))
+                .clamp(FLY_CAMERA_MIN_SPEED, FLY_CAMERA_MAX_SPEED);
+        }
+
+        // update rotation
+        let delta = mouse_motion.delta * settings.mouse_sensitivity * MOUSE_SENSITIVITY_SCALE;
+        let delta_y = if settings.invert_y { -delta.y } else { delta.y };
+
+        camera.rotation.x = (camera.rotation.x - delta_y).clamp(-FRAC_PI_2 + 0.01, FRAC_PI_2 - 0.01);
+        camera.rotation.y -= delta.x;
+        transform.rotation = Quat::from_euler(EulerRot::YXZ, camera.rotation.y, camera.rotation.x, 0.0);
+
+        // update position
+        let mut direction = Vec3::ZERO;
+        if keys.pressed(KeyCode::KeyW) { direction += *transform.forward() }
+        if keys.pressed(KeyCode::KeyS) { direction -= *transform.forward() }
+        if keys.pressed(KeyCode::KeyD) { direction += *transform.right() }
+        if keys.pressed(KeyCode::KeyA) { direction -= *transform.right() }
+        if keys.pressed(KeyCode::Space) { direction += Vec3::Y }
+        if keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) { direction -= Vec3::Y }
+
+        let mut speed = camera.speed;
+        if keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+            speed *= 3.0;
+        }
+
+        // camera uses real time, so it works with any timescale
+        transform.translation += direction.normalize_or_zero() * speed * time.delta_secs();
+    }
+
+    // helper functions
+
+    /// Exponential smoothing of value to target
+    #[inline] fn smooth(value: f32, target: f32, smoothness: f32, dt: f32) -> f32 {
+        value + (target - value) * (1.0 - (-smoothness * dt).exp())
+    }
 }
 
+/// Catmull-rom interpolation between p1 and p2
+#[inline] fn catmull_rom(p0: Vec3, p1: Vec3, p2: Vec3, p3: Vec3, t: f32) -> Vec3 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+
+    0.5 * (
+        2.0 * p1
+        + (p2 - p0) * t
+        + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+        + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3
+    )
+}
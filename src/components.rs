@@ -1,6 +1,12 @@
 //! This module contains various components that
 //! can't be placed in a separate module for various reasons.
 use bevy::prelude::*;
+use educe::Educe;
+
+/// Systems which react on game's hotkeys, they don't run
+/// while keyboard is captured (for example by developer console)
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct HotkeySystems;
 
 /// Sphere of tear - this is the component that determines
 /// in what radius the player receives fear.
@@ -21,6 +27,41 @@ impl SphereOfTear {
     }
 }
 
+/// Marker of monster, it can be set in blender
+#[derive(Component, Clone, Copy, Default, Reflect)]
+#[reflect(Component)]
+pub struct Monster;
+
+/// Volume of ladder, player inside it climbs up and down.
+/// Climbing player faces ladder's forward (-Z), it can be set in blender
+#[derive(Component, Clone, Copy, Debug, Educe, Reflect)]
+#[reflect(Component)]
+#[educe(Default)]
+#[require(Transform)]
+pub struct Ladder {
+    #[educe(Default = Vec3::new(0.5, 2.0, 0.5))] /// half size of ladder's volume (in local space)
+    pub half_extents: Vec3,
+
+    #[educe(Default = 1.5)] /// speed of climbing
+    pub climb_speed: f32,
+}
+
+impl Ladder {
+    /// Return point in ladder's local space
+    pub fn local_point(&self, ladder_transform: &GlobalTransform, point: Vec3) -> Vec3 {
+        ladder_transform.affine().inverse().transform_point3(point)
+    }
+
+    /// Return true if point is inside ladder's volume
+    pub fn contains(&self, ladder_transform: &GlobalTransform, point: Vec3) -> bool {
+        self.local_point(ladder_transform, point).abs().cmple(self.half_extents).all()
+    }
+}
+
+/// Marker of level's scene, it's despawned when other level is loaded
+#[derive(Component)]
+pub struct LevelScene;
+
 /// Marker that objects in Inventory
 #[derive(Component)]
 pub struct InInventory;
@@ -28,3 +69,116 @@ pub struct InInventory;
 #[derive(Component)]
 pub struct CanPickable;
 
+/// Item which can be stored in inventory
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct Item {
+    /// key of localized item's name
+    pub name: String,
+}
+
+
+/// Type of surface, it changes sound of steps
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum SurfaceType {
+    #[default]
+    Default,
+    Grass,
+    Dirt,
+    Wood,
+    Stone,
+    Metal,
+    Water,
+    Ice,
+    Mud,
+}
+
+impl SurfaceType {
+    /// All surface types
+    pub const ALL: [SurfaceType; 9] = [
+        SurfaceType::Default, SurfaceType::Grass, SurfaceType::Dirt, SurfaceType::Wood,
+        SurfaceType::Stone, SurfaceType::Metal, SurfaceType::Water, SurfaceType::Ice,
+        SurfaceType::Mud,
+    ];
+
+    /// Return name of surface (used in assets paths)
+    pub fn name(&self) -> &'static str {
+        match self {
+            SurfaceType::Default => "default",
+            SurfaceType::Grass => "grass",
+            SurfaceType::Dirt => "dirt",
+            SurfaceType::Wood => "wood",
+            SurfaceType::Stone => "stone",
+            SurfaceType::Metal => "metal",
+            SurfaceType::Water => "water",
+            SurfaceType::Ice => "ice",
+            SurfaceType::Mud => "mud",
+        }
+    }
+
+    /// Return multiplier of acceleration and friction on surface
+    pub fn traction(&self) -> f32 {
+        match self {
+            SurfaceType::Ice => 0.1,
+            SurfaceType::Mud => 0.6,
+            SurfaceType::Water => 0.8,
+            _ => 1.0,
+        }
+    }
+
+    /// Return multiplier of player's speed on surface
+    pub fn speed_multiplier(&self) -> f32 {
+        match self {
+            SurfaceType::Mud => 0.6,
+            SurfaceType::Water => 0.8,
+            _ => 1.0,
+        }
+    }
+}
+
+/// Material of ground's surface, it can be set
+/// in blender on object with collider (or on it's parent)
+#[derive(Component, Clone, Copy, Default, Reflect)]
+#[reflect(Component)]
+pub struct SurfaceMaterial(pub SurfaceType);
+
+impl SurfaceMaterial {
+    /// Find surface of entity: check entity and it's parents
+    pub fn find(
+        entity: Entity,
+        surfaces: &Query<&SurfaceMaterial>,
+        parents: &Query<&Parent>,
+    ) -> SurfaceType {
+        let mut current = entity;
+
+        // scenes from gltf store components on node, and colliders on mesh
+        for _ in 0..3 {
+            if let Ok(surface) = surfaces.get(current) {
+                return surface.0;
+            }
+
+            match parents.get(current) {
+                Ok(parent) => current = parent.get(),
+                Err(_) => break,
+            }
+        }
+
+        SurfaceType::Default
+    }
+}
+
+/// Return weight of box zone in point: 1.0 - inside, 0.0 - far outside.
+/// Weight falls linearly on `fade_distance` outside of box.
+pub fn box_zone_weight(half_extents: Vec3, fade_distance: f32, zone_transform: &GlobalTransform, point: Vec3) -> f32 {
+    let local = zone_transform.affine().inverse().transform_point3(point);
+    let outside = (local.abs() - half_extents).max(Vec3::ZERO);
+
+    // convert distance back to world scale
+    let distance = (outside * zone_transform.scale()).length();
+
+    if fade_distance <= 0.0 {
+        return if distance <= 0.0 { 1.0 } else { 0.0 };
+    }
+
+    (1.0 - distance / fade_distance).clamp(0.0, 1.0)
+}
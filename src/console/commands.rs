@@ -0,0 +1,211 @@
+//! implementation of console commands
+
+// import crates
+use bevy::prelude::*;
+
+// import data from this crate
+use crate::components::{InInventory, Item};
+use crate::interaction::ItemPickedUp;
+use crate::levels::structures::{LevelManager, LevelRegistry, LoadLevel};
+use crate::player::structures::{PlayerCameraPivot, PlayerComponent, PlayerControllerData};
+use crate::weather::structures::{SetTimeOfDay, SetWeather, WeatherKind};
+use super::structures::*;
+
+/// Create registry with all default commands
+pub fn default_commands() -> ConsoleCommands {
+    let mut commands = ConsoleCommands::default();
+
+    commands
+        .register("help", "help - show all commands", help)
+        .register("clear", "clear - clear console's log", clear)
+        .register("tp", "tp <x> <y> <z> - teleport player", teleport)
+        .register("noclip", "noclip - fly through geometry", noclip)
+        .register("god", "god - freeze player's fear", god)
+        .register("give", "give <item> - add item to inventory", give)
+        .register("set", "set fear <n> - set player's value", set)
+        .register("spawn", "spawn <prefab> - spawn gltf/<prefab>.glb in front of player", spawn)
+        .register("load_level", "load_level <id> - load level from registry", load_level)
+        .register("weather", "weather <clear|fog|rain|storm|auto> - force weather", weather)
+        .register("time", "time <hours> - set time of day", time_of_day)
+        .register("timescale", "timescale <f> - set speed of game's time", timescale);
+
+    commands
+}
+
+/// show all commands
+fn help(world: &mut World, _args: &[&str]) -> CommandResult {
+    let registry = world.resource::<ConsoleCommands>();
+    let lines: Vec<&str> = registry.complete("").iter()
+        .filter_map(|name| registry.0.get(name))
+        .map(|command| command.help)
+        .collect();
+
+    Ok(lines.join("\n"))
+}
+
+/// clear console's log
+fn clear(world: &mut World, _args: &[&str]) -> CommandResult {
+    world.resource_mut::<ConsoleState>().log.clear();
+    Ok(String::new())
+}
+
+/// teleport player
+fn teleport(world: &mut World, args: &[&str]) -> CommandResult {
+    let [x, y, z] = args else { return Err("usage: tp <x> <y> <z>".into()); };
+    let position = Vec3::new(parse_f32(x)?, parse_f32(y)?, parse_f32(z)?);
+
+    let mut query = world.query::<(&mut Transform, &mut PlayerControllerData)>();
+    let (mut transform, mut controller) = query.get_single_mut(world)
+        .map_err(|_| "player doesn't exist")?;
+
+    transform.translation = position;
+    controller.velocity = Vec3::ZERO;
+    controller.acceleration = Vec3::ZERO;
+
+    Ok(format!("teleported to {position}"))
+}
+
+/// toggle noclip mode
+fn noclip(world: &mut World, _args: &[&str]) -> CommandResult {
+    let mut query = world.query::<&mut PlayerControllerData>();
+    let mut controller = query.get_single_mut(world).map_err(|_| "player doesn't exist")?;
+
+    controller.noclip = !controller.noclip;
+    Ok(format!("noclip: {}", controller.noclip))
+}
+
+/// toggle freezing of player's fear
+fn god(world: &mut World, _args: &[&str]) -> CommandResult {
+    let mut query = world.query::<&mut PlayerComponent>();
+    let mut player = query.get_single_mut(world).map_err(|_| "player doesn't exist")?;
+
+    player.fear_frozen = !player.fear_frozen;
+    Ok(format!("god: {}", player.fear_frozen))
+}
+
+/// add item to inventory
+fn give(world: &mut World, args: &[&str]) -> CommandResult {
+    let [name] = args else { return Err("usage: give <item>".into()); };
+
+    let entity = world.spawn((
+        Name::new(format!("Item {name}")),
+        Item { name: name.to_string() },
+        InInventory,
+    )).id();
+
+    world.send_event(ItemPickedUp { entity, name: name.to_string() });
+    Ok(format!("gave {name}"))
+}
+
+/// set player's value
+fn set(world: &mut World, args: &[&str]) -> CommandResult {
+    match args {
+        ["fear", value] => {
+            let value = parse_f32(value)?;
+            let mut query = world.query::<&mut PlayerComponent>();
+            let mut player = query.get_single_mut(world).map_err(|_| "player doesn't exist")?;
+
+            player.fear = value.max(0.0);
+            Ok(format!("fear = {}", player.fear))
+        },
+        _ => Err("usage: set fear <n>".into()),
+    }
+}
+
+/// spawn prefab in front of player
+fn spawn(world: &mut World, args: &[&str]) -> CommandResult {
+    let [prefab] = args else { return Err("usage: spawn <prefab>".into()); };
+
+    let mut query = world.query_filtered::<&GlobalTransform, With<PlayerCameraPivot>>();
+    let camera = query.get_single(world).map_err(|_| "player doesn't exist")?;
+    let position = camera.translation() + camera.forward().with_y(0.0).normalize_or_zero() * 3.0;
+
+    let scene = world.resource::<AssetServer>().load(format!("gltf/{prefab}.glb#Scene0"));
+    world.spawn((
+        Name::new(format!("Prefab {prefab}")),
+        SceneRoot(scene),
+        Transform::from_translation(position),
+    ));
+
+    Ok(format!("spawned {prefab} at {position}"))
+}
+
+/// replace current level with other from registry
+fn load_level(world: &mut World, args: &[&str]) -> CommandResult {
+    let [id] = args else { return Err("usage: load_level <id>".into()); };
+
+    let manager = world.resource::<LevelManager>();
+    let registry = world.resource::<Assets<LevelRegistry>>().get(&manager.registry)
+        .ok_or("registry of levels isn't loaded")?;
+
+    if registry.get(id).is_none() {
+        let ids: Vec<&str> = registry.levels.iter().map(|level| level.id.as_str()).collect();
+        return Err(format!("unknown level, levels: {}", ids.join(", ")));
+    }
+
+    world.send_event(LoadLevel(id.to_string()));
+    Ok(format!("loading level {id}"))
+}
+
+/// force weather or return it to automatic changes
+fn weather(world: &mut World, args: &[&str]) -> CommandResult {
+    let [name] = args else { return Err("usage: weather <clear|fog|rain|storm|auto>".into()); };
+
+    if *name == "auto" {
+        world.send_event(SetWeather { kind: None, forced: false });
+        return Ok("weather changes automatically".into());
+    }
+
+    let kind = WeatherKind::from_name(name).ok_or("unknown weather, weathers: clear, fog, rain, storm, auto")?;
+    world.send_event(SetWeather { kind: Some(kind), forced: true });
+    Ok(format!("weather = {}", kind.name()))
+}
+
+/// set time of day
+fn time_of_day(world: &mut World, args: &[&str]) -> CommandResult {
+    let [hours] = args else { return Err("usage: time <hours>".into()); };
+    let hours = parse_f32(hours)?;
+
+    world.send_event(SetTimeOfDay(hours));
+    Ok(format!("time = {hours}"))
+}
+
+/// set speed of game's time
+fn timescale(world: &mut World, args: &[&str]) -> CommandResult {
+    let [value] = args else { return Err("usage: timescale <f>".into()); };
+    let value = parse_f32(value)?;
+
+    if value < 0.0 {
+        return Err("timescale can't be negative".into());
+    }
+
+    world.resource_mut::<Time<Virtual>>().set_relative_speed(value);
+    Ok(format!("timescale = {value}"))
+}
+
+// helper functions
+
+/// Parse argument as finite f32, infinity and NaN break time and transforms
+#[inline] fn parse_f32(value: &str) -> Result<f32, String> {
+    value.parse::<f32>().ok()
+        .filter(|value| value.is_finite())
+        .ok_or_else(|| format!("\"{value}\" is not a number"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_f32_accepts_finite_numbers() {
+        assert_eq!(parse_f32("1.5"), Ok(1.5));
+        assert_eq!(parse_f32("-3"), Ok(-3.0));
+    }
+
+    #[test]
+    fn parse_f32_rejects_not_finite_numbers() {
+        for value in ["inf", "-inf", "infinity", "nan", "NaN", "abc", ""] {
+            assert!(parse_f32(value).is_err(), "\"{value}\" is accepted");
+        }
+    }
+}
@@ -0,0 +1,31 @@
+//! Developer console - drop-down console (opened by backtick)
+//! with registry of commands. It exists only in debug builds.
+
+// import crates
+use bevy::prelude::*;
+
+use crate::components::HotkeySystems;
+
+// console systems and commands
+pub(super) mod systems;
+pub(super) mod commands;
+pub mod structures;
+
+pub struct ConsolePlugin;
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(structures::ConsoleState::default());
+        app.insert_resource(commands::default_commands());
+
+        // hotkeys don't work while player types command
+        app.configure_sets(Update, HotkeySystems.run_if(systems::console_closed));
+
+        app.add_systems(Startup, systems::setup_console);
+        app.add_systems(Update, (
+            systems::toggle_console,
+            systems::read_console_input,
+            systems::execute_console_commands,
+            systems::update_console_text,
+        ).chain());
+    }
+}
@@ -0,0 +1,85 @@
+//! This module store console's structures
+use bevy::{prelude::*, utils::HashMap};
+
+/// max count of lines in console's log
+pub(super) const MAX_LOG_LINES: usize = 18;
+
+/// Result of command: text for log or error
+pub type CommandResult = Result<String, String>;
+
+/// Command of console
+#[derive(Clone, Copy)]
+pub struct ConsoleCommand {
+    /// usage and description of command
+    pub help: &'static str,
+    /// function of command, it gets arguments without command's name
+    pub run: fn(&mut World, &[&str]) -> CommandResult,
+}
+
+/// Registry of all console's commands
+#[derive(Resource, Default)]
+pub struct ConsoleCommands(pub HashMap<&'static str, ConsoleCommand>);
+
+impl ConsoleCommands {
+    /// Add command to registry
+    pub fn register(
+        &mut self,
+        name: &'static str,
+        help: &'static str,
+        run: fn(&mut World, &[&str]) -> CommandResult,
+    ) -> &mut Self {
+        self.0.insert(name, ConsoleCommand { help, run });
+        self
+    }
+
+    /// Return sorted names of commands which start with prefix
+    pub fn complete(&self, prefix: &str) -> Vec<&'static str> {
+        let mut names: Vec<&'static str> = self.0.keys()
+            .filter(|name| name.starts_with(prefix))
+            .copied()
+            .collect();
+
+        names.sort_unstable();
+        names
+    }
+}
+
+/// State of console
+#[derive(Resource, Default)]
+pub struct ConsoleState {
+    /// console is open
+    pub open: bool,
+    /// player's input was enabled before console opened
+    pub input_was_enabled: bool,
+    /// current line
+    pub input: String,
+    /// entered lines
+    pub history: Vec<String>,
+    /// position in history while player browses it
+    pub history_index: Option<usize>,
+    /// output of commands
+    pub log: Vec<String>,
+    /// lines which wait for execution
+    pub pending: Vec<String>,
+}
+
+impl ConsoleState {
+    /// Add line to log
+    pub fn print(&mut self, line: impl Into<String>) {
+        self.log.push(line.into());
+
+        if self.log.len() > MAX_LOG_LINES {
+            let overflow = self.log.len() - MAX_LOG_LINES;
+            self.log.drain(..overflow);
+        }
+    }
+}
+
+#[derive(Component)] /// root node of console
+pub struct ConsoleRoot;
+
+#[derive(Component)] /// text of console's log
+pub struct ConsoleLogText;
+
+#[derive(Component)] /// text of console's input line
+pub struct ConsoleInputText;
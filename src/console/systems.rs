@@ -0,0 +1,214 @@
+//! implementation of console systems
+
+// import crates
+use bevy::{input::keyboard::{Key, KeyboardInput}, prelude::*};
+
+// import data from this crate
+use crate::player::structures::{InputOwner, InputOwners, PlayerInputEnabled};
+use super::structures::*;
+
+/// create hidden console
+pub fn setup_console(
+    mut commands: Commands,
+) {
+    commands.spawn((
+        Name::new("Console"),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(45.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::FlexEnd,
+            padding: UiRect::all(Val::Px(8.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.02, 0.02, 0.02, 0.85)),
+        GlobalZIndex(100),
+        Visibility::Hidden,
+        ConsoleRoot,
+    )).with_children(|parent| {
+        parent.spawn((
+            TextFont { font_size: 18.0, ..default() },
+            Text::new(""),
+            ConsoleLogText,
+        ));
+
+        parent.spawn((
+            TextFont { font_size: 20.0, ..default() },
+            TextColor(Color::srgb(0.9, 0.8, 0.3)),
+            Text::new("> "),
+            ConsoleInputText,
+        ));
+    });
+}
+
+/// run condition: console is closed
+pub fn console_closed(console: Res<ConsoleState>) -> bool {
+    !console.open
+}
+
+/// open and close console by backtick
+pub fn toggle_console(
+    mut console_query: Query<&mut Visibility, With<ConsoleRoot>>,
+    mut input_enabled: ResMut<PlayerInputEnabled>,
+    mut input_owners: ResMut<InputOwners>,
+    mut console: ResMut<ConsoleState>,
+    keys: Res<ButtonInput<KeyCode>>,
+) {
+    if !keys.just_pressed(KeyCode::Backquote) {
+        return;
+    }
+
+    console.open = !console.open;
+
+    // player can't move while console is open
+    if console.open {
+        console.input_was_enabled = input_enabled.0;
+        input_enabled.0 = false;
+        input_owners.0.insert(InputOwner::Console);
+    } else {
+        input_enabled.0 = console.input_was_enabled;
+        input_owners.0.remove(&InputOwner::Console);
+    }
+
+    for mut visibility in console_query.iter_mut() {
+        *visibility = if console.open { Visibility::Inherited } else { Visibility::Hidden };
+    }
+}
+
+/// type text, browse history and complete commands
+pub fn read_console_input(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut console: ResMut<ConsoleState>,
+    registry: Res<ConsoleCommands>,
+) {
+    if !console.open {
+        keyboard_events.clear();
+        return;
+    }
+
+    for event in keyboard_events.read() {
+        if !event.state.is_pressed() || event.key_code == KeyCode::Backquote {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Character(text) => console.input.push_str(text),
+            Key::Space => console.input.push(' '),
+            Key::Backspace => { console.input.pop(); },
+            Key::Enter => {
+                let line = std::mem::take(&mut console.input);
+                let line = line.trim().to_string();
+
+                if !line.is_empty() {
+                    console.history.push(line.clone());
+                    console.pending.push(line);
+                }
+                console.history_index = None;
+            },
+            Key::ArrowUp => {
+                if console.history.is_empty() {
+                    continue;
+                }
+
+                let index = match console.history_index {
+                    Some(index) => index.saturating_sub(1),
+                    None => console.history.len() - 1,
+                };
+                console.history_index = Some(index);
+                console.input = console.history[index].clone();
+            },
+            Key::ArrowDown => {
+                let Some(index) = console.history_index else { continue; };
+
+                if index + 1 < console.history.len() {
+                    console.history_index = Some(index + 1);
+                    console.input = console.history[index + 1].clone();
+                } else {
+                    console.history_index = None;
+                    console.input.clear();
+                }
+            },
+            Key::Tab => {
+                // complete only command's name
+                if console.input.contains(' ') {
+                    continue;
+                }
+
+                let candidates = registry.complete(&console.input);
+                match candidates.as_slice() {
+                    [] => {},
+                    [name] => console.input = format!("{name} "),
+                    names => {
+                        let prefix = common_prefix(names);
+                        let line = names.join("  ");
+
+                        console.input = prefix;
+                        console.print(line);
+                    },
+                }
+            },
+            _ => {},
+        }
+    }
+}
+
+/// execute entered commands, it needs full access to world
+pub fn execute_console_commands(world: &mut World) {
+    let pending = std::mem::take(&mut world.resource_mut::<ConsoleState>().pending);
+
+    for line in pending {
+        let parts: Vec<&str> = line.split_whitespace().collect();
+        let Some((name, args)) = parts.split_first() else { continue; };
+
+        let command = world.resource::<ConsoleCommands>().0.get(name).copied();
+        let output = match command {
+            Some(command) => (command.run)(world, args),
+            None => Err(format!("unknown command \"{name}\", type \"help\"")),
+        };
+
+        let mut console = world.resource_mut::<ConsoleState>();
+        console.print(format!("> {line}"));
+
+        match output {
+            Ok(text) if text.is_empty() => {},
+            Ok(text) => console.print(text),
+            Err(error) => console.print(format!("error: {error}")),
+        }
+    }
+}
+
+/// update texts of console
+pub fn update_console_text(
+    mut log_query: Query<&mut Text, (With<ConsoleLogText>, Without<ConsoleInputText>)>,
+    mut input_query: Query<&mut Text, (With<ConsoleInputText>, Without<ConsoleLogText>)>,
+    console: Res<ConsoleState>,
+) {
+    if !console.is_changed() {
+        return;
+    }
+
+    for mut text in log_query.iter_mut() {
+        **text = console.log.join("\n");
+    }
+
+    for mut text in input_query.iter_mut() {
+        **text = format!("> {}_", console.input);
+    }
+}
+
+// helper functions
+
+/// Return common prefix of all names
+fn common_prefix(names: &[&str]) -> String {
+    let Some(first) = names.first() else { return String::new(); };
+    let mut length = first.len();
+
+    for name in &names[1..] {
+        length = length.min(
+            first.chars().zip(name.chars()).take_while(|(a, b)| a == b).count()
+        );
+    }
+
+    first.chars().take(length).collect()
+}
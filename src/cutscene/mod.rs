@@ -0,0 +1,31 @@
+//! Cutscenes - timeline-based player of scripted sequences:
+//! camera moves, entity animations, audio cues and subtitles.
+//! Sequences are stored in `assets/sequences/*.sequence.ron`.
+
+// import crates
+use bevy::prelude::*;
+
+use crate::ron_loader::RonLoader;
+
+// cutscene systems
+pub(super) mod systems;
+pub mod structures;
+
+pub struct CutscenePlugin;
+impl Plugin for CutscenePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<structures::Sequence>();
+        app.register_asset_loader(RonLoader::<structures::Sequence>::new(&["sequence.ron"]));
+
+        app.insert_resource(structures::SequencePlayer::default());
+        app.add_event::<structures::PlaySequence>();
+        app.add_event::<structures::SequenceFinished>();
+
+        app.add_systems(Startup, systems::setup_subtitles);
+        app.add_systems(Update, (
+            systems::play_triggered_sequences,
+            systems::start_sequences,
+            systems::update_sequence,
+        ).chain().before(crate::camera::camera_systems::switch_camera_mode));
+    }
+}
@@ -0,0 +1,207 @@
+//! This module store cutscene's structures and data of sequences
+use bevy::prelude::*;
+use serde::{Deserialize, Deserializer};
+
+use crate::audio::structures::MixerBus;
+
+/// Data with time, sequence's lists of it are sorted by time on load
+pub trait Timed {
+    /// Return time (in seconds)
+    fn time(&self) -> f32;
+}
+
+/// Key of camera's path
+#[derive(Clone, Debug, Deserialize)]
+pub struct CameraKey {
+    /// time of key (in seconds)
+    pub time: f32,
+    /// position of camera
+    pub position: [f32; 3],
+    /// point at which camera looks
+    pub look_at: [f32; 3],
+}
+
+/// Key of entity's transform
+#[derive(Clone, Debug, Deserialize)]
+pub struct TransformKey {
+    /// time of key (in seconds)
+    pub time: f32,
+    /// translation of entity
+    pub translation: [f32; 3],
+    /// rotation of entity (euler angles in degrees, YXZ)
+    #[serde(default)]
+    pub rotation: [f32; 3],
+}
+
+/// Animation of entity, entity is found by it's name
+#[derive(Clone, Debug, Deserialize)]
+pub struct AnimationTrack {
+    /// name of entity
+    pub entity: String,
+    /// keys of animation
+    #[serde(deserialize_with = "sorted_by_time")]
+    pub keys: Vec<TransformKey>,
+}
+
+/// Bus of audio cue (it mirrors mixer's buses)
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+pub enum CueBus {
+    #[default]
+    Sfx,
+    Ambience,
+    Music,
+    Voice,
+}
+
+impl From<CueBus> for MixerBus {
+    fn from(bus: CueBus) -> Self {
+        match bus {
+            CueBus::Sfx => MixerBus::Sfx,
+            CueBus::Ambience => MixerBus::Ambience,
+            CueBus::Music => MixerBus::Music,
+            CueBus::Voice => MixerBus::Voice,
+        }
+    }
+}
+
+/// Sound which plays at time
+#[derive(Clone, Debug, Deserialize)]
+pub struct AudioCue {
+    /// time of cue (in seconds)
+    pub time: f32,
+    /// path to sound
+    pub sound: String,
+    /// bus of sound
+    #[serde(default)]
+    pub bus: CueBus,
+    /// position of sound, if none sound isn't spatial
+    #[serde(default)]
+    pub position: Option<[f32; 3]>,
+}
+
+/// Line of subtitles
+#[derive(Clone, Debug, Deserialize)]
+pub struct SubtitleLine {
+    /// time of line (in seconds)
+    pub time: f32,
+    /// how long line is shown (in seconds)
+    pub duration: f32,
+    /// key of localized text
+    pub key: String,
+}
+
+/// Scripted sequence, it's loaded from `*.sequence.ron`
+#[derive(Asset, TypePath, Clone, Debug, Deserialize)]
+pub struct Sequence {
+    /// duration of sequence (in seconds)
+    pub duration: f32,
+    /// player can skip sequence
+    #[serde(default = "default_true")]
+    pub skippable: bool,
+    /// player's input is disabled while sequence plays
+    #[serde(default = "default_true")]
+    pub lock_input: bool,
+    /// keys of camera, if empty camera isn't changed
+    #[serde(default, deserialize_with = "sorted_by_time")]
+    pub camera: Vec<CameraKey>,
+    /// animations of entities
+    #[serde(default)]
+    pub animations: Vec<AnimationTrack>,
+    /// sounds, they are played in order of time
+    #[serde(default, deserialize_with = "sorted_by_time")]
+    pub audio: Vec<AudioCue>,
+    /// subtitles
+    #[serde(default)]
+    pub subtitles: Vec<SubtitleLine>,
+}
+
+impl Timed for CameraKey {
+    fn time(&self) -> f32 {
+        self.time
+    }
+}
+
+impl Timed for TransformKey {
+    fn time(&self) -> f32 {
+        self.time
+    }
+}
+
+impl Timed for AudioCue {
+    fn time(&self) -> f32 {
+        self.time
+    }
+}
+
+/// Event: play sequence by path (for example "sequences/first_encounter.sequence.ron")
+#[derive(Event, Clone, Debug)]
+pub struct PlaySequence(pub String);
+
+/// Event: sequence finished (or was skipped)
+#[derive(Event, Clone, Debug)]
+pub struct SequenceFinished(pub String);
+
+/// Sequence which plays now
+pub struct ActiveSequence {
+    /// path of sequence
+    pub path: String,
+    /// handle of sequence's asset
+    pub handle: Handle<Sequence>,
+    /// time from start (in seconds)
+    pub time: f32,
+    /// sequence started (asset is loaded)
+    pub started: bool,
+    /// count of played audio cues
+    pub played_cues: usize,
+    /// entity with cinematic path of camera
+    pub camera_rig: Option<Entity>,
+    /// player's input was enabled before sequence
+    pub input_was_enabled: bool,
+}
+
+/// Player of sequences, only one sequence plays at time
+#[derive(Resource, Default)]
+pub struct SequencePlayer {
+    /// current sequence
+    pub active: Option<ActiveSequence>,
+}
+
+#[derive(Component)] /// text of subtitles
+pub struct SubtitleText;
+
+// helper functions
+
+#[inline] fn default_true() -> bool {
+    true
+}
+
+/// Deserialize list and sort it by time
+#[inline] fn sorted_by_time<'de, D, T>(deserializer: D) -> Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + Timed,
+{
+    let mut items = Vec::<T>::deserialize(deserializer)?;
+    items.sort_by(|a, b| a.time().total_cmp(&b.time()));
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn audio_cues_are_sorted_by_time() {
+        let sequence: Sequence = ron::from_str(r#"(
+            duration: 5.0,
+            audio: [
+                (time: 3.0, sound: "c.ogg"),
+                (time: 1.0, sound: "a.ogg"),
+                (time: 2.0, sound: "b.ogg"),
+            ],
+        )"#).unwrap();
+
+        let sounds: Vec<_> = sequence.audio.iter().map(|cue| cue.sound.as_str()).collect();
+        assert_eq!(sounds, ["a.ogg", "b.ogg", "c.ogg"]);
+    }
+}
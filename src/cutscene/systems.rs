@@ -0,0 +1,289 @@
+//! implementation of cutscene systems
+
+// import crates
+use bevy::{asset::LoadState, audio::PlaybackMode, prelude::*};
+
+// import data from this crate
+use crate::audio::structures::{AudioEmitter, AudioMixer, LoadSound};
+use crate::camera::{CameraMode, CinematicPath, CinematicTarget, SetCameraMode};
+use crate::localization::structures::Localized;
+use crate::player::structures::{InputOwner, InputOwners, PlayerInputEnabled};
+use crate::triggers::structures::TriggerEntered;
+use super::structures::*;
+
+/// create hidden subtitles text
+pub fn setup_subtitles(
+    mut commands: Commands,
+) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            bottom: Val::Px(80.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        PickingBehavior::IGNORE,
+    )).with_child((
+        TextFont { font_size: 28.0, ..default() },
+        TextLayout::new_with_justify(JustifyText::Center),
+        Text::new(""),
+        Localized::default(),
+        Visibility::Hidden,
+        SubtitleText,
+    ));
+}
+
+/// play sequences of triggers with action "sequence:<path>"
+pub fn play_triggered_sequences(
+    mut entered: EventReader<TriggerEntered>,
+    mut play: EventWriter<PlaySequence>,
+) {
+    for event in entered.read() {
+        if let Some(path) = event.argument("sequence") {
+            play.send(PlaySequence(path.to_string()));
+        }
+    }
+}
+
+/// start requested sequence
+pub fn start_sequences(
+    mut play: EventReader<PlaySequence>,
+    mut player: ResMut<SequencePlayer>,
+    assets: Res<AssetServer>,
+) {
+    for PlaySequence(path) in play.read() {
+        if let Some(active) = &player.active {
+            warn!("can't play sequence \"{path}\", \"{}\" is playing", active.path);
+            continue;
+        }
+
+        player.active = Some(ActiveSequence {
+            path: path.clone(),
+            handle: assets.load(path.clone()),
+            time: 0.0,
+            started: false,
+            played_cues: 0,
+            camera_rig: None,
+            input_was_enabled: false,
+        });
+    }
+}
+
+/// play current sequence
+pub fn update_sequence(
+    mut commands: Commands,
+    mut player: ResMut<SequencePlayer>,
+    mut input_enabled: ResMut<PlayerInputEnabled>,
+    mut input_owners: ResMut<InputOwners>,
+    mut camera_mode: EventWriter<SetCameraMode>,
+    mut finished: EventWriter<SequenceFinished>,
+    mut named_query: Query<(&Name, &mut Transform), Without<CinematicPath>>,
+    mut rigs_query: Query<&mut CinematicPath>,
+    mut subtitles_query: Query<(&mut Localized, &mut Visibility), With<SubtitleText>>,
+    sequences: Res<Assets<Sequence>>,
+    assets: Res<AssetServer>,
+    mixer: Res<AudioMixer>,
+    keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+) {
+    let Some(active) = player.active.as_mut() else { return; };
+
+    let Some(sequence) = sequences.get(&active.handle) else {
+        // sequence can't be loaded
+        if matches!(assets.load_state(&active.handle), LoadState::Failed(_)) {
+            error!("sequence \"{}\" can't be loaded", active.path);
+            player.active = None;
+        }
+        return;
+    };
+
+    // start sequence, input is locked so right click can't enable it
+    let starting = !active.started;
+    if starting {
+        active.started = true;
+        active.input_was_enabled = input_enabled.0;
+
+        if sequence.lock_input {
+            input_enabled.0 = false;
+            input_owners.0.insert(InputOwner::Cutscene);
+        }
+
+        if let Some(rig) = spawn_camera_rig(&mut commands, sequence) {
+            active.camera_rig = Some(rig);
+            camera_mode.send(SetCameraMode(CameraMode::Cinematic(rig)));
+        }
+    }
+
+    // update time, sequence can be skipped by space or enter.
+    // Keys of start frame are ignored (player jumped into trigger)
+    let skipped = sequence.skippable && !starting && keys.any_just_pressed([KeyCode::Space, KeyCode::Enter]);
+    active.time = if skipped { sequence.duration } else { active.time + time.delta_secs() };
+    let current = active.time.min(sequence.duration);
+
+    // camera
+    if let Some(mut path) = active.camera_rig.and_then(|rig| rigs_query.get_mut(rig).ok()) {
+        let (param, look_at) = sample_camera(&sequence.camera, current);
+        path.elapsed = param * path.duration;
+        path.look_at = CinematicTarget::Point(look_at);
+    }
+
+    // animations of entities
+    for track in sequence.animations.iter() {
+        let Some(transform) = sample_transform(&track.keys, current) else { continue; };
+
+        for (name, mut entity_transform) in named_query.iter_mut() {
+            if name.as_str() == track.entity {
+                entity_transform.translation = transform.translation;
+                entity_transform.rotation = transform.rotation;
+            }
+        }
+    }
+
+    // audio cues (skipped cues aren't played)
+    while let Some(cue) = sequence.audio.get(active.played_cues) {
+        if cue.time > current {
+            break;
+        }
+        active.played_cues += 1;
+
+        if skipped {
+            continue;
+        }
+
+        let Some(sound) = assets.load_sound(cue.sound.clone()) else { continue; };
+
+        let position = cue.position.map(Vec3::from_array);
+        commands.spawn((
+            #[cfg(debug_assertions)] Name::new(format!("Cue {}", cue.sound)),
+            Transform::from_translation(position.unwrap_or_default()),
+            AudioEmitter::new(cue.bus.into(), 1.0).sound(
+                sound, PlaybackMode::Despawn, position.is_some(), &mixer
+            ),
+        ));
+    }
+
+    // subtitles
+    let line = sequence.subtitles.iter()
+        .find(|line| current >= line.time && current < line.time + line.duration);
+
+    for (mut localized, mut visibility) in subtitles_query.iter_mut() {
+        match line {
+            Some(line) => {
+                if localized.key != line.key {
+                    *localized = Localized::new(line.key.clone());
+                }
+                *visibility = Visibility::Inherited;
+            },
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+
+    // finish sequence
+    if active.time < sequence.duration {
+        return;
+    }
+
+    if sequence.lock_input {
+        input_enabled.0 = active.input_was_enabled;
+        input_owners.0.remove(&InputOwner::Cutscene);
+    }
+
+    if let Some(rig) = active.camera_rig {
+        camera_mode.send(SetCameraMode(CameraMode::FirstPerson));
+        commands.entity(rig).despawn_recursive();
+    }
+
+    for (_, mut visibility) in subtitles_query.iter_mut() {
+        *visibility = Visibility::Hidden;
+    }
+
+    finished.send(SequenceFinished(active.path.clone()));
+    player.active = None;
+}
+
+// helper functions
+
+/// Spawn entity with camera's path, if sequence has camera keys
+fn spawn_camera_rig(commands: &mut Commands, sequence: &Sequence) -> Option<Entity> {
+    let first = sequence.camera.first()?;
+    let last = sequence.camera.last()?;
+
+    let points = sequence.camera.iter()
+        .map(|key| Vec3::from_array(key.position))
+        .collect();
+
+    let mut path = CinematicPath::new(
+        points,
+        (last.time - first.time).max(0.0),
+        CinematicTarget::Point(Vec3::from_array(first.look_at)),
+    );
+    path.auto_advance = false;
+
+    Some(commands.spawn((
+        #[cfg(debug_assertions)] Name::new("Cutscene camera"),
+        Transform::default(),
+        path,
+    )).id())
+}
+
+/// Return parameter of camera's path (0.0 - 1.0) and look at point in time.
+/// Keys are placed uniformly on path, so time is mapped to segments.
+fn sample_camera(keys: &[CameraKey], time: f32) -> (f32, Vec3) {
+    let Some(first) = keys.first() else { return (0.0, Vec3::ZERO); };
+    if keys.len() == 1 || time <= first.time {
+        return (0.0, Vec3::from_array(first.look_at));
+    }
+
+    let segments = (keys.len() - 1) as f32;
+    for (index, pair) in keys.windows(2).enumerate() {
+        let (from, to) = (&pair[0], &pair[1]);
+        if time > to.time {
+            continue;
+        }
+
+        let local = inverse_lerp(from.time, to.time, time);
+        let look_at = Vec3::from_array(from.look_at).lerp(Vec3::from_array(to.look_at), local);
+
+        return ((index as f32 + local) / segments, look_at);
+    }
+
+    (1.0, Vec3::from_array(keys[keys.len() - 1].look_at))
+}
+
+/// Return transform of animation in time
+fn sample_transform(keys: &[TransformKey], time: f32) -> Option<Transform> {
+    let to_transform = |key: &TransformKey| {
+        let [x, y, z] = key.rotation.map(f32::to_radians);
+        Transform::from_translation(Vec3::from_array(key.translation))
+            .with_rotation(Quat::from_euler(EulerRot::YXZ, y, x, z))
+    };
+
+    let first = keys.first()?;
+    if time <= first.time {
+        return Some(to_transform(first));
+    }
+
+    for pair in keys.windows(2) {
+        if time > pair[1].time {
+            continue;
+        }
+
+        let (from, to) = (to_transform(&pair[0]), to_transform(&pair[1]));
+        let t = inverse_lerp(pair[0].time, pair[1].time, time);
+
+        return Some(Transform::from_translation(from.translation.lerp(to.translation, t))
+            .with_rotation(from.rotation.slerp(to.rotation, t)));
+    }
+
+    keys.last().map(to_transform)
+}
+
+/// Return position of value between a and b (0.0 - 1.0)
+#[inline] fn inverse_lerp(a: f32, b: f32, value: f32) -> f32 {
+    if b - a <= f32::EPSILON {
+        return 1.0;
+    }
+
+    ((value - a) / (b - a)).clamp(0.0, 1.0)
+}
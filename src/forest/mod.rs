@@ -0,0 +1,21 @@
+//! Forest - seeded procedural generation of trees, rocks,
+//! undergrowth and fog over streamed forest chunks
+
+// import crates
+use bevy::prelude::*;
+
+// forest systems
+pub(super) mod systems;
+pub mod structures;
+
+pub struct ForestPlugin;
+impl Plugin for ForestPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<structures::ForestSettings>();
+        app.insert_resource(structures::ForestSettings::default());
+
+        app.add_systems(Startup, systems::setup_forest_assets);
+        app.add_systems(Update, systems::generate_forest_chunks
+            .after(crate::levels::systems::stream_forest_chunks));
+    }
+}
@@ -0,0 +1,254 @@
+//! This module store forest's structures and generator
+use bevy::prelude::*;
+use educe::Educe;
+use serde::Deserialize;
+
+use crate::random::{splitmix64, SeededRng};
+
+/// Kind of generated object
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ForestKind {
+    /// tree, it has collider on trunk
+    Tree,
+    /// rock, it has collider
+    Rock,
+    /// bush or grass, without collider
+    Undergrowth,
+    /// volume of fog
+    FogEmitter,
+}
+
+impl ForestKind {
+    /// Return radius of object, which must be outside of exclusion zones
+    pub fn radius(self) -> f32 {
+        match self {
+            ForestKind::Tree => 2.0,
+            ForestKind::Rock => 1.0,
+            ForestKind::Undergrowth => 0.5,
+            ForestKind::FogEmitter => 0.0,
+        }
+    }
+}
+
+/// Generated object of forest (in chunk's space)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ForestInstance {
+    pub kind: ForestKind,
+    pub position: Vec3,
+    /// rotation around Y
+    pub rotation: f32,
+    pub scale: f32,
+}
+
+/// Zone around authored location, where forest isn't generated
+#[derive(Clone, Debug, Deserialize)]
+pub struct ExclusionZone {
+    /// center of zone (x, z)
+    pub center: [f32; 2],
+    pub radius: f32,
+}
+
+/// Path from world map, it's kept clear of trees
+#[derive(Clone, Debug, Deserialize)]
+pub struct ForestPath {
+    /// points of path (x, z)
+    pub points: Vec<[f32; 2]>,
+    pub width: f32,
+}
+
+/// Areas, where forest isn't generated
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct ForestLayout {
+    #[serde(default)]
+    pub exclusions: Vec<ExclusionZone>,
+    #[serde(default)]
+    pub paths: Vec<ForestPath>,
+}
+
+impl ForestLayout {
+    /// Return true if object with radius at position (x, z) is in exclusion zone or on path
+    pub fn is_excluded(&self, position: Vec2, radius: f32) -> bool {
+        let in_zone = self.exclusions.iter()
+            .any(|zone| position.distance(Vec2::from_array(zone.center)) < zone.radius + radius);
+
+        let on_path = self.paths.iter().any(|path| {
+            path.points.windows(2).any(|segment| {
+                let distance = segment_distance(position, Vec2::from_array(segment[0]), Vec2::from_array(segment[1]));
+                distance < path.width / 2.0 + radius
+            })
+        });
+
+        in_zone || on_path
+    }
+}
+
+/// Settings of forest generator
+#[derive(Resource, Clone, Debug, Educe, Reflect)]
+#[reflect(Resource)]
+#[educe(Default)]
+pub struct ForestSettings {
+    #[educe(Default = 0xF0_2E57)] /// seed of forest
+    pub seed: u64,
+
+    #[educe(Default = 6.0)] /// size of cell with one tree
+    pub tree_spacing: f32,
+
+    #[educe(Default = 0.7)] /// chance of tree in cell
+    pub tree_chance: f32,
+
+    #[educe(Default = 0.004)] /// rocks per square meter
+    pub rock_density: f32,
+
+    #[educe(Default = 0.03)] /// undergrowth per square meter
+    pub undergrowth_density: f32,
+
+    #[educe(Default = 2)] /// fog emitters in chunk
+    pub fog_emitters: u32,
+}
+
+/// Forest chunk is generated
+#[derive(Component)]
+pub struct ForestGenerated;
+
+/// Shared meshes and materials of forest,
+/// trees and undergrowth are drawn by vegetation
+#[derive(Resource)]
+pub struct ForestAssets {
+    pub rock_mesh: Handle<Mesh>,
+    pub rock_material: Handle<StandardMaterial>,
+}
+
+/// Generate objects of chunk. The same seed, chunk and layout
+/// always give the same objects, so forest doesn't need to be saved.
+pub fn generate_chunk(settings: &ForestSettings, layout: &ForestLayout, coord: IVec2, size: f32) -> Vec<ForestInstance> {
+    let mut rng = SeededRng::new(chunk_seed(settings.seed, coord));
+    let mut instances = Vec::new();
+    let origin = Vec2::new(coord.x as f32, coord.y as f32) * size;
+
+    // returns instance, if it isn't excluded
+    let mut place = |rng: &mut SeededRng, kind: ForestKind, local: Vec2, min_scale: f32, max_scale: f32| {
+        let rotation = rng.range(0.0, std::f32::consts::TAU);
+        let scale = rng.range(min_scale, max_scale);
+
+        if !layout.is_excluded(origin + local, kind.radius() * scale) {
+            instances.push(ForestInstance { kind, position: Vec3::new(local.x, 0.0, local.y), rotation, scale });
+        }
+    };
+
+    // trees on jittered grid, so they don't intersect
+    let cells = (size / settings.tree_spacing).floor().max(1.0) as i32;
+    let cell_size = size / cells as f32;
+    for x in 0..cells {
+        for z in 0..cells {
+            if !rng.chance(settings.tree_chance) {
+                continue;
+            }
+
+            let local = Vec2::new(
+                (x as f32 + rng.range(0.15, 0.85)) * cell_size,
+                (z as f32 + rng.range(0.15, 0.85)) * cell_size,
+            );
+            place(&mut rng, ForestKind::Tree, local, 0.8, 1.4);
+        }
+    }
+
+    // rocks, undergrowth and fog are scattered randomly
+    let area = size * size;
+    let counts = [
+        (ForestKind::Rock, (area * settings.rock_density) as u32, 0.5, 1.8),
+        (ForestKind::Undergrowth, (area * settings.undergrowth_density) as u32, 0.6, 1.3),
+        (ForestKind::FogEmitter, settings.fog_emitters, 0.8, 1.5),
+    ];
+
+    for (kind, count, min_scale, max_scale) in counts {
+        for _ in 0..count {
+            let local = Vec2::new(rng.range(0.0, size), rng.range(0.0, size));
+            place(&mut rng, kind, local, min_scale, max_scale);
+        }
+    }
+
+    instances
+}
+
+// helper functions
+/// Return seed of chunk, every coordinate is hashed separately,
+/// so neighbour and mirrored chunks get unrelated seeds
+#[inline]
+fn chunk_seed(seed: u64, coord: IVec2) -> u64 {
+    let x = splitmix64(seed.wrapping_add(u64::from(coord.x as u32)));
+    splitmix64(x.wrapping_add(0x9E37_79B9_7F4A_7C15).wrapping_add(u64::from(coord.y as u32)))
+}
+
+/// Return distance from point to segment
+#[inline]
+fn segment_distance(point: Vec2, start: Vec2, end: Vec2) -> f32 {
+    let segment = end - start;
+    let t = ((point - start).dot(segment) / segment.length_squared().max(f32::EPSILON)).clamp(0.0, 1.0);
+    point.distance(start + segment * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIZE: f32 = 64.0;
+
+    /// layout with zone in center of chunk (0, 0) and path along it's top
+    fn layout() -> ForestLayout {
+        ForestLayout {
+            exclusions: vec![ExclusionZone { center: [32.0, 32.0], radius: 12.0 }],
+            paths: vec![ForestPath { points: vec![[-10.0, 56.0], [80.0, 56.0]], width: 6.0 }],
+        }
+    }
+
+    #[test]
+    fn same_input_gives_same_chunk() {
+        let settings = ForestSettings::default();
+
+        assert_eq!(
+            generate_chunk(&settings, &layout(), IVec2::new(3, -2), SIZE),
+            generate_chunk(&settings, &layout(), IVec2::new(3, -2), SIZE),
+        );
+    }
+
+    #[test]
+    fn different_seed_gives_different_chunk() {
+        let first = ForestSettings { seed: 1, ..default() };
+        let second = ForestSettings { seed: 2, ..default() };
+
+        assert_ne!(
+            generate_chunk(&first, &layout(), IVec2::ZERO, SIZE),
+            generate_chunk(&second, &layout(), IVec2::ZERO, SIZE),
+        );
+    }
+
+    #[test]
+    fn mirrored_chunks_are_different() {
+        let settings = ForestSettings::default();
+        let empty = ForestLayout::default();
+
+        assert_ne!(
+            generate_chunk(&settings, &empty, IVec2::new(1, 0), SIZE),
+            generate_chunk(&settings, &empty, IVec2::new(-1, 0), SIZE),
+        );
+        assert_ne!(
+            generate_chunk(&settings, &empty, IVec2::new(2, 5), SIZE),
+            generate_chunk(&settings, &empty, IVec2::new(5, 2), SIZE),
+        );
+    }
+
+    #[test]
+    fn nothing_is_placed_in_exclusions_and_paths() {
+        let layout = layout();
+        let instances = generate_chunk(&ForestSettings::default(), &layout, IVec2::ZERO, SIZE);
+        assert!(!instances.is_empty());
+
+        for instance in instances {
+            let position = Vec2::new(instance.position.x, instance.position.z);
+            let radius = instance.kind.radius() * instance.scale;
+
+            assert!(position.distance(Vec2::new(32.0, 32.0)) >= 12.0 + radius, "{instance:?} is in zone");
+            assert!((position.y - 56.0).abs() >= 3.0 + radius, "{instance:?} is on path");
+        }
+    }
+}
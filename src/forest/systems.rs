@@ -0,0 +1,103 @@
+//! implementation of forest systems
+
+// import crates
+use bevy::{pbr::FogVolume, prelude::*};
+use bevy_rapier3d::prelude::*;
+
+// import data from this crate
+use crate::levels::structures::{ForestChunk, LevelManager, LevelRegistry};
+use crate::terrain::structures::Terrain;
+use crate::vegetation::structures::{VegetationInstance, VegetationLayer, VegetationSource};
+use super::structures::*;
+
+/// create shared meshes and materials of forest
+pub fn setup_forest_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(ForestAssets {
+        rock_mesh: meshes.add(Sphere::new(0.6).mesh().ico(1).unwrap()),
+        rock_material: materials.add(Color::srgb(0.35, 0.35, 0.33)),
+    });
+}
+
+/// generate objects of new forest chunks
+pub fn generate_forest_chunks(
+    mut commands: Commands,
+    chunks_query: Query<(Entity, &ForestChunk), Without<ForestGenerated>>,
+    registries: Res<Assets<LevelRegistry>>,
+    forest_assets: Option<Res<ForestAssets>>,
+    settings: Res<ForestSettings>,
+    manager: Res<LevelManager>,
+    terrain: Res<Terrain>,
+) {
+    let Some(forest_assets) = forest_assets else { return; };
+    let Some(registry) = registries.get(&manager.registry) else { return; };
+
+    // objects are placed on terrain
+    if terrain.data.is_none() {
+        return;
+    }
+
+    for (entity, chunk) in chunks_query.iter() {
+        let instances = generate_chunk(&settings, &registry.forest.layout, chunk.coord, chunk.size);
+        let origin = chunk.coord.as_vec2() * chunk.size;
+
+        let mut trees = Vec::new();
+        let mut undergrowth = Vec::new();
+
+        commands.entity(entity).insert(ForestGenerated).with_children(|parent| {
+            for instance in instances {
+                let height = terrain.height_at(origin + instance.position.xz());
+                let position = instance.position + Vec3::Y * height;
+                let transform = Transform::from_translation(position)
+                    .with_rotation(Quat::from_rotation_y(instance.rotation))
+                    .with_scale(Vec3::splat(instance.scale));
+
+                // vegetation is in world space
+                let vegetation = VegetationInstance::new(
+                    position + origin.extend(0.0).xzy(), instance.rotation, instance.scale
+                );
+
+                match instance.kind {
+                    ForestKind::Tree => {
+                        trees.push(vegetation);
+                        parent.spawn((
+                            transform.with_translation(position + Vec3::Y * 3.0 * instance.scale),
+                            Collider::cylinder(3.0, 0.25),
+                        ));
+                    },
+                    ForestKind::Undergrowth => undergrowth.push(vegetation),
+                    ForestKind::Rock => {
+                        parent.spawn((
+                            Mesh3d(forest_assets.rock_mesh.clone()),
+                            MeshMaterial3d(forest_assets.rock_material.clone()),
+                            transform,
+                            Collider::ball(0.6),
+                        ));
+                    },
+                    ForestKind::FogEmitter => {
+                        parent.spawn((
+                            FogVolume { density_factor: 0.05, ..default() },
+                            transform.with_scale(Vec3::new(20.0, 4.0, 20.0) * instance.scale),
+                        ));
+                    },
+                }
+            }
+
+            // trees are drawn by two layers with the same instances
+            for (layer, instances) in [
+                (VegetationLayer::Trunk, trees.clone()),
+                (VegetationLayer::Crown, trees),
+                (VegetationLayer::Undergrowth, undergrowth),
+            ] {
+                parent.spawn((
+                    Transform::default(),
+                    Visibility::default(),
+                    VegetationSource::new(layer, instances),
+                ));
+            }
+        });
+    }
+}
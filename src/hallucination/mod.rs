@@ -0,0 +1,27 @@
+//! Hallucinations - when player's fear is high the world
+//! stops being reliable. This module spawns phantoms
+//! (silhouettes, fake sounds, flickering lights) around the player.
+
+// import crates
+use bevy::prelude::*;
+
+// director of hallucinations
+pub(super) mod systems;
+pub mod structures;
+
+pub struct HallucinationPlugin;
+impl Plugin for HallucinationPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<structures::HallucinationSettings>();
+
+        let settings = structures::HallucinationSettings::default();
+        app.insert_resource(structures::HallucinationDirector::new(settings.seed));
+        app.insert_resource(settings);
+
+        app.add_systems(Update, (
+            systems::direct_hallucinations,
+            systems::update_phantoms,
+            systems::flicker_phantom_lights,
+        ).chain());
+    }
+}
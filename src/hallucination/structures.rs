@@ -0,0 +1,287 @@
+//! This module store hallucination's structures and resources
+use std::time::Duration;
+
+use bevy::{prelude::*, utils::HashMap};
+use educe::Educe;
+
+use crate::random::SeededRng;
+
+// const's
+
+// phantom's light flicker
+pub(super) const FLICKER_INTERVAL: f32 = 0.05;
+pub(super) const FLICKER_CHANCE: f32 = 0.45;
+
+/// All kinds of phantoms which director can spawn
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+pub enum PhantomKind {
+    /// black figure standing near the player
+    Silhouette,
+    /// sound of steps behind the player
+    Footsteps,
+    /// light which flickers and goes out
+    FlickeringLight,
+    /// sound of slammed door
+    DoorSlam,
+}
+
+/// Rule for spawn one kind of phantoms
+#[derive(Clone, Debug, Reflect)]
+pub struct PhantomRule {
+    /// kind of phantom
+    pub kind: PhantomKind,
+    /// minimal player's fear for spawn this phantom
+    pub min_fear: f32,
+    /// time (in seconds) before this kind can be spawned again
+    pub cooldown: f32,
+    /// how long phantom lives (in seconds)
+    pub lifetime: f32,
+}
+
+/// Settings of hallucination director
+#[derive(Resource, Clone, Debug, Educe, Reflect)]
+#[reflect(Resource)]
+#[educe(Default)]
+pub struct HallucinationSettings {
+    #[educe(Default = 0x5EED)] /// seed of director's random
+    pub seed: u64,
+
+    #[educe(Default = 6.0)] /// time between rolls at minimal fear (in seconds)
+    pub roll_interval: f32,
+
+    #[educe(Default = 0.35)] /// roll interval multiplier at maximal fear
+    pub max_fear_interval_scale: f32,
+
+    #[educe(Default = 100.0)] /// fear at which interval is minimal
+    pub max_fear: f32,
+
+    #[educe(Default = 4.0)] /// minimal distance from camera to phantom
+    pub min_distance: f32,
+
+    #[educe(Default = 9.0)] /// maximal distance from camera to phantom
+    pub max_distance: f32,
+
+    #[educe(Default = 25.0_f32.to_radians())]
+    /// how far (in radians) outside of view frustum phantom can be spawned
+    pub frustum_margin: f32,
+
+    #[educe(Default = 12.0_f32.to_radians())]
+    /// if angle between camera forward and phantom less than this, phantom vanishes
+    pub look_angle: f32,
+
+    #[educe(Default = vec![
+        PhantomRule { kind: PhantomKind::Footsteps, min_fear: 20.0, cooldown: 15.0, lifetime: 3.0 },
+        PhantomRule { kind: PhantomKind::FlickeringLight, min_fear: 35.0, cooldown: 20.0, lifetime: 4.0 },
+        PhantomRule { kind: PhantomKind::Silhouette, min_fear: 50.0, cooldown: 30.0, lifetime: 6.0 },
+        PhantomRule { kind: PhantomKind::DoorSlam, min_fear: 70.0, cooldown: 45.0, lifetime: 2.5 },
+    ])]
+    /// rules of phantoms
+    pub rules: Vec<PhantomRule>,
+}
+
+impl HallucinationSettings {
+    /// Return minimal fear at which any phantom can be spawned
+    pub fn min_fear(&self) -> f32 {
+        self.rules.iter()
+            .map(|rule| rule.min_fear)
+            .fold(f32::INFINITY, f32::min)
+    }
+}
+
+/// State of hallucination director
+#[derive(Resource)]
+pub struct HallucinationDirector {
+    /// random generator, reset it with same seed for reproduce hallucinations
+    pub rng: SeededRng,
+    /// time before next roll (in seconds)
+    pub next_roll: f32,
+    /// remaining cooldowns of phantom kinds (in seconds)
+    pub cooldowns: HashMap<PhantomKind, f32>,
+}
+
+impl HallucinationDirector {
+    /// Create director with seed
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: SeededRng::new(seed),
+            next_roll: 0.0,
+            cooldowns: HashMap::new(),
+        }
+    }
+
+    /// Return true if this phantom kind can be spawned
+    pub fn is_ready(&self, kind: PhantomKind) -> bool {
+        self.cooldowns.get(&kind).copied().unwrap_or(0.0) <= 0.0
+    }
+
+    /// Update timers and roll phantom if it's time. The same seed
+    /// and the same fear and delta times give the same phantoms.
+    pub fn update(&mut self, settings: &HallucinationSettings, fear: f32, dt: f32) -> Option<PhantomRoll> {
+        // update cooldowns
+        for cooldown in self.cooldowns.values_mut() {
+            *cooldown -= dt;
+        }
+
+        // fear is too small for hallucinations
+        if fear < settings.min_fear() {
+            return None;
+        }
+
+        self.next_roll -= dt;
+        if self.next_roll > 0.0 {
+            return None;
+        }
+
+        // the more fear, the more often rolls
+        let fear_factor = (fear / settings.max_fear).clamp(0.0, 1.0);
+        let interval_scale = 1.0 - (1.0 - settings.max_fear_interval_scale) * fear_factor;
+        self.next_roll = settings.roll_interval * interval_scale;
+
+        // choose phantom from ready rules
+        let ready: Vec<&PhantomRule> = settings.rules.iter()
+            .filter(|rule| fear >= rule.min_fear && self.is_ready(rule.kind))
+            .collect();
+
+        if ready.is_empty() {
+            return None;
+        }
+
+        let rule = ready[self.rng.index(ready.len())];
+        self.cooldowns.insert(rule.kind, rule.cooldown);
+
+        // choose side and distance just outside of view frustum
+        let mut frustum_offset = self.rng.range(0.05, settings.frustum_margin);
+        if self.rng.chance(0.5) {
+            frustum_offset = -frustum_offset;
+        }
+
+        Some(PhantomRoll {
+            kind: rule.kind,
+            lifetime: rule.lifetime,
+            frustum_offset,
+            distance: self.rng.range(settings.min_distance, settings.max_distance),
+            seed: self.rng.next_u64(),
+        })
+    }
+}
+
+/// Phantom chosen by director
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PhantomRoll {
+    /// kind of phantom
+    pub kind: PhantomKind,
+    /// how long phantom lives (in seconds)
+    pub lifetime: f32,
+    /// angle outside of view frustum (in radians), negative is left side
+    pub frustum_offset: f32,
+    /// distance from camera
+    pub distance: f32,
+    /// seed of phantom's own random
+    pub seed: u64,
+}
+
+/// Short-lived phantom entity
+#[derive(Component)]
+#[require(Transform, Visibility)]
+pub struct Phantom {
+    /// kind of this phantom
+    pub kind: PhantomKind,
+    /// when timer finished phantom will be despawned
+    pub lifetime: Timer,
+}
+
+impl Phantom {
+    /// Create phantom which lives `lifetime` seconds
+    pub fn new(kind: PhantomKind, lifetime: f32) -> Self {
+        Self { kind, lifetime: Timer::from_seconds(lifetime, TimerMode::Once) }
+    }
+}
+
+/// Phantom's flickering light, it has own random so
+/// flicker doesn't depend on frame rate and director's random
+#[derive(Component)]
+pub struct PhantomFlicker {
+    /// light intensity when light is on
+    pub intensity: f32,
+    /// light is on
+    pub on: bool,
+    /// every tick light can be switched
+    pub timer: Timer,
+    /// random of this light
+    pub rng: SeededRng,
+}
+
+impl PhantomFlicker {
+    /// Create flicker with random seed
+    pub fn new(intensity: f32, seed: u64) -> Self {
+        Self {
+            intensity,
+            on: true,
+            timer: Timer::from_seconds(FLICKER_INTERVAL, TimerMode::Repeating),
+            rng: SeededRng::new(seed),
+        }
+    }
+
+    /// Advance flicker and return current light intensity
+    pub fn tick(&mut self, delta: Duration) -> f32 {
+        self.timer.tick(delta);
+
+        for _ in 0..self.timer.times_finished_this_tick() {
+            if self.rng.chance(FLICKER_CHANCE) {
+                self.on = !self.on;
+            }
+        }
+
+        if self.on { self.intensity } else { 0.0 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// roll phantoms with constant fear and frame time
+    fn schedule(seed: u64, fear: f32, dt: f32, frames: usize) -> Vec<PhantomRoll> {
+        let settings = HallucinationSettings { seed, ..default() };
+        let mut director = HallucinationDirector::new(settings.seed);
+
+        (0..frames)
+            .filter_map(|_| director.update(&settings, fear, dt))
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_gives_same_schedule() {
+        let first = schedule(42, 80.0, 1.0 / 60.0, 60 * 300);
+
+        assert!(!first.is_empty());
+        assert_eq!(first, schedule(42, 80.0, 1.0 / 60.0, 60 * 300));
+    }
+
+    #[test]
+    fn different_seed_gives_different_schedule() {
+        assert_ne!(
+            schedule(1, 80.0, 1.0 / 60.0, 60 * 300),
+            schedule(2, 80.0, 1.0 / 60.0, 60 * 300),
+        );
+    }
+
+    #[test]
+    fn low_fear_gives_no_phantoms() {
+        assert!(schedule(42, 5.0, 1.0 / 60.0, 60 * 300).is_empty());
+    }
+
+    #[test]
+    fn flicker_does_not_depend_on_frame_rate() {
+        let mut fast = PhantomFlicker::new(1.0, 7);
+        let mut slow = PhantomFlicker::new(1.0, 7);
+
+        for frame in 0..200 {
+            let fast_intensity = fast.tick(Duration::from_millis(10));
+
+            if frame % 5 == 4 {
+                assert_eq!(fast_intensity, slow.tick(Duration::from_millis(50)));
+            }
+        }
+    }
+}
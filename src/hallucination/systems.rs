@@ -0,0 +1,129 @@
+//! implementation of hallucination systems
+use std::f32::consts::PI;
+
+// import crates
+use bevy::{audio::PlaybackMode, prelude::*};
+use autodefault::autodefault;
+
+// import data from this crate
+use crate::audio::structures::{AudioEmitter, AudioMixer, LoadSound, MixerBus};
+use crate::player::structures::{PlayerCameraPivot, PlayerComponent};
+use crate::random::SeededRng;
+use super::structures::*;
+
+/// roll phantoms while player's fear is high
+#[autodefault]
+pub fn direct_hallucinations(
+    mut commands: Commands,
+    mut director: ResMut<HallucinationDirector>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    camera_query: Query<(&GlobalTransform, &Projection), With<PlayerCameraPivot>>,
+    player_query: Query<&PlayerComponent>,
+    settings: Res<HallucinationSettings>,
+    assets: Res<AssetServer>,
+    mixer: Res<AudioMixer>,
+    time: Res<Time>,
+) {
+    let Ok(player) = player_query.get_single() else { return; };
+    let Ok((camera_transform, projection)) = camera_query.get_single() else { return; };
+
+    let Some(roll) = director.update(&settings, player.fear, time.delta_secs()) else { return; };
+
+    // get half of horizontal fov
+    let half_fov = match projection {
+        Projection::Perspective(perspective) =>
+            ((perspective.fov / 2.0).tan() * perspective.aspect_ratio).atan(),
+        Projection::Orthographic(_) => PI / 4.0,
+    };
+
+    // choose position just outside of view frustum
+    let yaw = (half_fov + roll.frustum_offset.abs()).copysign(roll.frustum_offset);
+
+    let forward = camera_transform.forward().with_y(0.0).normalize_or(Vec3::NEG_Z);
+    let direction = Quat::from_rotation_y(yaw) * forward;
+
+    let mut position = camera_transform.translation() + direction * roll.distance;
+    position.y -= 1.5;
+
+    // spawn phantom
+    let mut entity = commands.spawn((
+        #[cfg(debug_assertions)] Name::new(format!("Phantom {:?}", roll.kind)),
+        Transform::from_translation(position)
+            .looking_at(camera_transform.translation().with_y(position.y), Vec3::Y),
+        Phantom::new(roll.kind, roll.lifetime),
+    ));
+
+    match roll.kind {
+        PhantomKind::Silhouette => {
+            entity.insert((
+                Mesh3d(meshes.add(Capsule3d::new(0.3, 1.3))),
+                MeshMaterial3d(materials.add(StandardMaterial {
+                    base_color: Color::BLACK,
+                    unlit: true,
+                })),
+            ));
+        },
+        PhantomKind::Footsteps => {
+            if let Some(sound) = assets.load_sound("audio/hallucinations/footsteps.ogg") {
+                entity.insert(AudioEmitter::new(MixerBus::Sfx, 1.0).sound(
+                    sound, PlaybackMode::Despawn, true, &mixer
+                ));
+            }
+        },
+        PhantomKind::FlickeringLight => {
+            let mut rng = SeededRng::new(roll.seed);
+            let intensity = rng.range(20_000.0, 60_000.0);
+            entity.insert((
+                PointLight {
+                    color: Color::srgb(1.0, 0.85, 0.6),
+                    intensity,
+                    range: 8.0,
+                },
+                PhantomFlicker::new(intensity, rng.next_u64()),
+            ));
+        },
+        PhantomKind::DoorSlam => {
+            if let Some(sound) = assets.load_sound("audio/hallucinations/door_slam.ogg") {
+                entity.insert(AudioEmitter::new(MixerBus::Sfx, 1.0).sound(
+                    sound, PlaybackMode::Despawn, true, &mixer
+                ));
+            }
+        },
+    }
+}
+
+/// despawn phantoms when they are old, or when player looks at them
+pub fn update_phantoms(
+    mut commands: Commands,
+    mut phantoms_query: Query<(Entity, &GlobalTransform, &mut Phantom)>,
+    camera_query: Query<&GlobalTransform, With<PlayerCameraPivot>>,
+    settings: Res<HallucinationSettings>,
+    time: Res<Time>,
+) {
+    let camera_transform = camera_query.get_single().ok();
+
+    for (entity, transform, mut phantom) in phantoms_query.iter_mut() {
+        phantom.lifetime.tick(time.delta());
+
+        // phantom is looked at directly
+        let looked_at = camera_transform.is_some_and(|camera| {
+            let to_phantom = transform.translation() - camera.translation();
+            camera.forward().angle_between(to_phantom) < settings.look_angle
+        });
+
+        if phantom.lifetime.finished() || looked_at {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// randomly turn on and off phantom's lights
+pub fn flicker_phantom_lights(
+    mut lights_query: Query<(&mut PointLight, &mut PhantomFlicker)>,
+    time: Res<Time>,
+) {
+    for (mut light, mut flicker) in lights_query.iter_mut() {
+        light.intensity = flicker.tick(time.delta());
+    }
+}
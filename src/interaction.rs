@@ -0,0 +1,137 @@
+//! Interaction - player looks at objects with `Interactable`
+//! component and interacts with them by pressing E
+
+// import crates
+use bevy::prelude::*;
+
+/// max distance from camera to interactable object
+pub(crate) const INTERACTION_DISTANCE: f32 = 2.5;
+/// key of interaction and it's name in prompts
+pub(crate) const INTERACTION_KEY: KeyCode = KeyCode::KeyE;
+pub(crate) const INTERACTION_KEY_NAME: &str = "E";
+
+/// Object which player can interact with
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct Interactable {
+    /// key of localized prompt (for example "interact-pick-up")
+    pub prompt: String,
+}
+
+/// Interactable object at which player looks now
+#[derive(Resource, Default)]
+pub struct InteractionFocus(pub Option<Entity>);
+
+/// Event: player interacted with object
+#[derive(Event, Clone, Copy, Debug)]
+pub struct Interacted(pub Entity);
+
+/// Event: player picked up item
+#[derive(Event, Clone, Debug)]
+pub struct ItemPickedUp {
+    /// picked entity
+    pub entity: Entity,
+    /// key of localized item's name
+    pub name: String,
+}
+
+pub struct InteractionPlugin;
+impl Plugin for InteractionPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Interactable>();
+        app.insert_resource(InteractionFocus::default());
+        app.add_event::<Interacted>();
+        app.add_event::<ItemPickedUp>();
+
+        app.add_systems(Update, (
+            interaction_systems::update_focus,
+            interaction_systems::interact,
+            interaction_systems::pick_up_items,
+        ).chain());
+    }
+}
+
+pub mod interaction_systems {
+    //! implementation of interaction systems
+    // import crates
+    use bevy::prelude::*;
+
+    // import data from this crate
+    use crate::components::{CanPickable, InInventory, Item};
+    use crate::player::structures::{PlayerCameraPivot, PlayerComponent, PlayerInputEnabled};
+    use super::*;
+
+    /// find interactable object in front of camera
+    pub fn update_focus(
+        mut ray_cast: MeshRayCast,
+        mut focus: ResMut<InteractionFocus>,
+        camera_query: Query<&GlobalTransform, With<PlayerCameraPivot>>,
+        player_query: Query<Entity, With<PlayerComponent>>,
+        interactables: Query<(), With<Interactable>>,
+        parents: Query<&Parent>,
+    ) {
+        let Ok(camera_transform) = camera_query.get_single() else {
+            focus.0 = None;
+            return;
+        };
+
+        let player = player_query.get_single().ok();
+        let ray = Ray3d::new(camera_transform.translation(), camera_transform.forward());
+        let filter = |entity: Entity| Some(entity) != player;
+        let settings = RayCastSettings::default()
+            .with_filter(&filter)
+            .always_early_exit();
+
+        // take nearest hit and find interactable in it or it's parents
+        let hit = ray_cast.cast_ray(ray, &settings).first()
+            .filter(|(_, hit)| hit.distance <= INTERACTION_DISTANCE)
+            .map(|(entity, _)| *entity);
+
+        let new_focus = hit.and_then(|entity| {
+            std::iter::once(entity)
+                .chain(parents.iter_ancestors(entity))
+                .find(|entity| interactables.contains(*entity))
+        });
+
+        if focus.0 != new_focus {
+            focus.0 = new_focus;
+        }
+    }
+
+    /// interact with focused object by E
+    pub fn interact(
+        mut interacted: EventWriter<Interacted>,
+        input_enabled: Res<PlayerInputEnabled>,
+        keys: Res<ButtonInput<KeyCode>>,
+        focus: Res<InteractionFocus>,
+    ) {
+        if !input_enabled.0 || !keys.just_pressed(INTERACTION_KEY) {
+            return;
+        }
+
+        if let Some(entity) = focus.0 {
+            interacted.send(Interacted(entity));
+        }
+    }
+
+    /// move pickable items to inventory
+    pub fn pick_up_items(
+        mut commands: Commands,
+        mut interacted: EventReader<Interacted>,
+        mut picked: EventWriter<ItemPickedUp>,
+        items_query: Query<Option<&Item>, With<CanPickable>>,
+    ) {
+        for Interacted(entity) in interacted.read() {
+            let Ok(item) = items_query.get(*entity) else { continue; };
+
+            commands.entity(*entity)
+                .remove::<CanPickable>()
+                .insert((InInventory, Visibility::Hidden));
+
+            picked.send(ItemPickedUp {
+                entity: *entity,
+                name: item.map(|item| item.name.clone()).unwrap_or_default(),
+            });
+        }
+    }
+}
@@ -0,0 +1,35 @@
+//! Levels - registry of game's locations (`assets/levels/`),
+//! transitions between them with loading screen and streaming
+//! of forest chunks around player
+
+// import crates
+use bevy::prelude::*;
+
+use crate::ron_loader::RonLoader;
+use crate::save::structures::SaveSet;
+
+// level systems
+pub(super) mod systems;
+pub mod structures;
+
+pub struct LevelsPlugin;
+impl Plugin for LevelsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<structures::LevelRegistry>();
+        app.register_asset_loader(RonLoader::<structures::LevelRegistry>::new(&["levels.ron"]));
+
+        app.add_event::<structures::LoadLevel>();
+        app.add_event::<structures::LevelLoaded>();
+        app.insert_resource(structures::ForestStreamer::default());
+
+        app.add_systems(Startup, (systems::load_level_registry, systems::setup_loading_screen));
+        app.add_systems(Update, (
+            systems::start_first_level,
+            systems::level_transition_triggers,
+            systems::begin_level_loading,
+            systems::update_level_loading,
+            systems::stream_forest_chunks,
+        ).chain());
+        app.add_systems(Update, systems::sync_level_save.in_set(SaveSet::Sync));
+    }
+}
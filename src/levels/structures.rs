@@ -0,0 +1,117 @@
+//! This module store level's structures and data
+use bevy::{prelude::*, utils::HashMap};
+use serde::Deserialize;
+
+use crate::forest::structures::ForestLayout;
+
+// const's
+/// path to registry of levels
+pub(super) const LEVEL_REGISTRY: &str = "levels/world.levels.ron";
+/// time after level's colliders are created, before player is released (in seconds)
+pub(super) const LEVEL_SETTLE_TIME: f32 = 0.5;
+
+/// Definition of level
+#[derive(Clone, Debug, Deserialize)]
+pub struct LevelDef {
+    /// id of level
+    pub id: String,
+    /// key of localized name
+    pub name: String,
+    /// path to scene of level
+    pub scene: String,
+    /// position of player after loading
+    pub spawn: [f32; 3],
+    /// forest chunks are streamed around player
+    #[serde(default)]
+    pub forest: bool,
+    /// name of atmosphere profile
+    #[serde(default)]
+    pub atmosphere: Option<String>,
+}
+
+/// Settings of forest streaming
+#[derive(Clone, Debug, Deserialize)]
+pub struct ForestDef {
+    /// size of chunk's side
+    pub chunk_size: f32,
+    /// chunks in this radius (in chunks) around player are loaded
+    pub radius: i32,
+    /// authored scenes of chunks, they are added to generated forest
+    #[serde(default)]
+    pub scenes: HashMap<(i32, i32), String>,
+    /// exclusion zones and paths of generated forest
+    #[serde(default)]
+    pub layout: ForestLayout,
+}
+
+/// Registry of levels, it's loaded from `*.levels.ron`
+#[derive(Asset, TypePath, Clone, Debug, Deserialize)]
+pub struct LevelRegistry {
+    /// id of first level
+    pub start: String,
+    pub levels: Vec<LevelDef>,
+    pub forest: ForestDef,
+}
+
+impl LevelRegistry {
+    /// Return level by id
+    pub fn get(&self, id: &str) -> Option<&LevelDef> {
+        self.levels.iter().find(|level| level.id == id)
+    }
+}
+
+/// Level, which is loading now
+pub struct LevelLoading {
+    /// id of level
+    pub id: String,
+    /// scene of level
+    pub scene: Handle<Scene>,
+    /// scene is spawned, colliders are creating
+    pub spawned: bool,
+    /// timer after colliders are created
+    pub settle: Timer,
+    /// player's input was enabled before loading
+    pub input_was_enabled: bool,
+}
+
+/// State of levels
+#[derive(Resource)]
+pub struct LevelManager {
+    /// registry of levels
+    pub registry: Handle<LevelRegistry>,
+    /// id of current level
+    pub current: Option<String>,
+    /// level, which is loading now
+    pub loading: Option<LevelLoading>,
+    /// first level is requested
+    pub started: bool,
+}
+
+/// Loaded forest chunks
+#[derive(Resource, Default)]
+pub struct ForestStreamer {
+    pub chunks: HashMap<IVec2, Entity>,
+}
+
+/// Chunk of forest
+#[derive(Component, Clone, Copy, Debug)]
+pub struct ForestChunk {
+    /// coordinate of chunk
+    pub coord: IVec2,
+    /// size of chunk's side
+    pub size: f32,
+}
+
+/// Event: load level by id
+#[derive(Event, Clone, Debug)]
+pub struct LoadLevel(pub String);
+
+/// Event: level is loaded
+#[derive(Event, Clone, Debug)]
+pub struct LevelLoaded(pub String);
+
+#[derive(Component)] /// root of loading screen
+pub struct LoadingScreen;
+
+#[derive(Component)] /// text of loading screen
+pub struct LoadingText;
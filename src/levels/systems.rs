@@ -0,0 +1,309 @@
+//! implementation of level systems
+
+// import crates
+use bevy::{asset::LoadState, prelude::*};
+use bevy_rapier3d::prelude::*;
+
+// import data from this crate
+use crate::components::LevelScene;
+use crate::localization::structures::{Localization, Localized};
+use crate::player::structures::{PlayerComponent, PlayerControllerData, PlayerInputEnabled};
+use crate::save::structures::{GameLoaded, SaveData, SaveGame};
+use crate::triggers::structures::TriggerEntered;
+use super::structures::*;
+
+/// load registry of levels
+pub fn load_level_registry(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    commands.insert_resource(LevelManager {
+        registry: asset_server.load(LEVEL_REGISTRY),
+        current: None,
+        loading: None,
+        started: false,
+    });
+}
+
+/// create hidden loading screen
+pub fn setup_loading_screen(
+    mut commands: Commands,
+) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::FlexEnd,
+            justify_content: JustifyContent::FlexEnd,
+            padding: UiRect::all(Val::Px(40.0)),
+            ..default()
+        },
+        BackgroundColor(Color::BLACK),
+        GlobalZIndex(10),
+        Visibility::Hidden,
+        LoadingScreen,
+    )).with_child((
+        TextFont { font_size: 28.0, ..default() },
+        Text::new(""),
+        Localized::default(),
+        LoadingText,
+    ));
+}
+
+/// load first level, when registry is loaded
+pub fn start_first_level(
+    mut load: EventWriter<LoadLevel>,
+    mut manager: ResMut<LevelManager>,
+    registries: Res<Assets<LevelRegistry>>,
+) {
+    if manager.started {
+        return;
+    }
+
+    let Some(registry) = registries.get(&manager.registry) else { return; };
+    load.send(LoadLevel(registry.start.clone()));
+    manager.started = true;
+}
+
+/// load levels of triggers with action "level:<id>"
+pub fn level_transition_triggers(
+    mut entered: EventReader<TriggerEntered>,
+    mut load: EventWriter<LoadLevel>,
+) {
+    for event in entered.read() {
+        if let Some(id) = event.argument("level") {
+            load.send(LoadLevel(id.to_string()));
+        }
+    }
+}
+
+/// start loading of requested level, current level
+/// stays until scene of new level is loaded
+pub fn begin_level_loading(
+    mut load: EventReader<LoadLevel>,
+    mut screen_query: Query<&mut Visibility, With<LoadingScreen>>,
+    mut text_query: Query<&mut Localized, With<LoadingText>>,
+    mut player_query: Query<&mut PlayerControllerData, With<PlayerComponent>>,
+    mut input_enabled: ResMut<PlayerInputEnabled>,
+    mut manager: ResMut<LevelManager>,
+    registries: Res<Assets<LevelRegistry>>,
+    localization: Res<Localization>,
+    asset_server: Res<AssetServer>,
+) {
+    let Some(LoadLevel(id)) = load.read().last() else { return; };
+    let Some(registry) = registries.get(&manager.registry) else { return; };
+
+    let Some(level) = registry.get(id) else {
+        warn!("level \"{id}\" doesn't exist");
+        return;
+    };
+
+    if manager.loading.is_some() {
+        warn!("can't load level \"{id}\", other level is loading");
+        return;
+    }
+
+    manager.loading = Some(LevelLoading {
+        id: id.clone(),
+        scene: asset_server.load(&level.scene),
+        spawned: false,
+        settle: Timer::from_seconds(LEVEL_SETTLE_TIME, TimerMode::Once),
+        input_was_enabled: input_enabled.0,
+    });
+    input_enabled.0 = false;
+
+    // player waits for colliders of new level
+    for mut controller in player_query.iter_mut() {
+        controller.frozen = true;
+    }
+
+    for mut visibility in screen_query.iter_mut() {
+        *visibility = Visibility::Inherited;
+    }
+
+    for mut localized in text_query.iter_mut() {
+        *localized = Localized::new("loading-level").with_arg("level", localization.get(&level.name));
+    }
+}
+
+/// replace current level by loaded, move player to it's spawn and hide loading screen
+pub fn update_level_loading(
+    mut commands: Commands,
+    mut screen_query: Query<&mut Visibility, With<LoadingScreen>>,
+    mut player_query: Query<(&mut Transform, &mut PlayerControllerData), With<PlayerComponent>>,
+    mut input_enabled: ResMut<PlayerInputEnabled>,
+    mut loaded: EventWriter<LevelLoaded>,
+    mut manager: ResMut<LevelManager>,
+    mut streamer: ResMut<ForestStreamer>,
+    levels_query: Query<Entity, Or<(With<LevelScene>, With<ForestChunk>)>>,
+    scenes_query: Query<(), (With<LevelScene>, With<AsyncSceneCollider>)>,
+    registries: Res<Assets<LevelRegistry>>,
+    asset_server: Res<AssetServer>,
+    time: Res<Time>,
+) {
+    let Some(registry) = registries.get(&manager.registry) else { return; };
+    let Some(loading) = manager.loading.as_mut() else { return; };
+    let Some(level) = registry.get(&loading.id) else { return; };
+
+    if !loading.spawned {
+        match asset_server.load_state(&loading.scene) {
+            LoadState::Loaded => (),
+            LoadState::Failed(error) => {
+                // current level isn't despawned, so player stays in it
+                error!("can't load level \"{}\": {error}", loading.id);
+                input_enabled.0 = loading.input_was_enabled;
+                manager.loading = None;
+
+                for (_, mut controller) in player_query.iter_mut() {
+                    controller.frozen = false;
+                }
+
+                for mut visibility in screen_query.iter_mut() {
+                    *visibility = Visibility::Hidden;
+                }
+                return;
+            },
+            _ => return,
+        }
+
+        // despawn current level and forest
+        for entity in levels_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        streamer.chunks.clear();
+
+        commands.spawn((
+            Name::new(format!("Level {}", level.id)),
+            SceneRoot(loading.scene.clone()),
+            RigidBody::Fixed, Transform::default(),
+            AsyncSceneCollider::default(),
+            LevelScene,
+        ));
+
+        // move player to spawn of level
+        for (mut transform, mut controller) in player_query.iter_mut() {
+            transform.translation = Vec3::from_array(level.spawn);
+            controller.velocity = Vec3::ZERO;
+            controller.acceleration = Vec3::ZERO;
+        }
+
+        loading.spawned = true;
+        manager.current = None;
+        return;
+    }
+
+    // wait while colliders are created, rapier removes
+    // `AsyncSceneCollider` when scene's colliders exist
+    if !scenes_query.is_empty() || !loading.settle.tick(time.delta()).finished() {
+        return;
+    }
+
+    for (_, mut controller) in player_query.iter_mut() {
+        controller.frozen = false;
+    }
+
+    input_enabled.0 = loading.input_was_enabled;
+    loaded.send(LevelLoaded(loading.id.clone()));
+    info!("level \"{}\" is loaded", loading.id);
+
+    manager.current = Some(loading.id.clone());
+    manager.loading = None;
+
+    for mut visibility in screen_query.iter_mut() {
+        *visibility = Visibility::Hidden;
+    }
+}
+
+/// load forest chunks around player and unload far chunks
+pub fn stream_forest_chunks(
+    mut commands: Commands,
+    mut streamer: ResMut<ForestStreamer>,
+    player_query: Query<&Transform, With<PlayerComponent>>,
+    registries: Res<Assets<LevelRegistry>>,
+    asset_server: Res<AssetServer>,
+    manager: Res<LevelManager>,
+) {
+    let Some(registry) = registries.get(&manager.registry) else { return; };
+    let Some(level) = manager.current.as_deref().and_then(|id| registry.get(id)) else { return; };
+    let Ok(player_transform) = player_query.get_single() else { return; };
+
+    if !level.forest {
+        return;
+    }
+
+    let forest = &registry.forest;
+    let center = chunk_coord(player_transform.translation, forest.chunk_size);
+
+    // unload chunks, they are unloaded a bit further than loaded
+    streamer.chunks.retain(|coord, entity| {
+        let keep = chunk_distance(*coord, center) <= forest.radius + 1;
+        if !keep {
+            commands.entity(*entity).despawn_recursive();
+        }
+        keep
+    });
+
+    // load new chunks
+    for x in -forest.radius..=forest.radius {
+        for z in -forest.radius..=forest.radius {
+            let coord = center + IVec2::new(x, z);
+            if streamer.chunks.contains_key(&coord) {
+                continue;
+            }
+
+            let mut chunk = commands.spawn((
+                Name::new(format!("Forest chunk {} {}", coord.x, coord.y)),
+                Transform::from_xyz(coord.x as f32 * forest.chunk_size, 0.0, coord.y as f32 * forest.chunk_size),
+                Visibility::default(),
+                ForestChunk { coord, size: forest.chunk_size },
+            ));
+
+            // authored scenes are loaded asynchronously by asset server
+            if let Some(scene) = forest.scenes.get(&(coord.x, coord.y)) {
+                chunk.insert((
+                    SceneRoot(asset_server.load(scene)),
+                    RigidBody::Fixed,
+                    AsyncSceneCollider::default(),
+                ));
+            }
+
+            streamer.chunks.insert(coord, chunk.id());
+        }
+    }
+}
+
+/// write current level to save and load it after loading
+pub fn sync_level_save(
+    mut save: EventReader<SaveGame>,
+    mut loaded: EventReader<GameLoaded>,
+    mut load: EventWriter<LoadLevel>,
+    mut data: ResMut<SaveData>,
+    manager: Res<LevelManager>,
+) {
+    if save.read().count() > 0 {
+        data.level = manager.current.clone();
+    }
+
+    if loaded.read().count() > 0 {
+        if let Some(level) = data.level.clone().filter(|level| manager.current.as_ref() != Some(level)) {
+            load.send(LoadLevel(level));
+        }
+    }
+}
+
+// helper functions
+/// Return coordinate of chunk with position
+#[inline]
+fn chunk_coord(position: Vec3, chunk_size: f32) -> IVec2 {
+    IVec2::new(
+        (position.x / chunk_size).floor() as i32,
+        (position.z / chunk_size).floor() as i32,
+    )
+}
+
+/// Return distance between chunks (in chunks)
+#[inline]
+fn chunk_distance(first: IVec2, second: IVec2) -> i32 {
+    (first - second).abs().max_element()
+}
@@ -16,6 +16,32 @@ pub(crate) mod systems;
 pub(crate) mod player;
 pub(crate) mod camera;
 pub(crate) mod ui;
+pub(crate) mod hallucination;
+pub(crate) mod random;
+pub(crate) mod ron_loader;
+pub(crate) mod audio;
+pub(crate) mod settings;
+pub(crate) mod localization;
+pub(crate) mod interaction;
+pub(crate) mod cutscene;
+pub(crate) mod triggers;
+pub(crate) mod save;
+pub(crate) mod objectives;
+pub(crate) mod notes;
+pub(crate) mod levels;
+pub(crate) mod forest;
+pub(crate) mod terrain;
+pub(crate) mod vegetation;
+pub(crate) mod atmosphere;
+pub(crate) mod weather;
+pub(crate) mod lights;
+
+// add modules if dev build
+#[cfg(debug_assertions)]
+pub(crate) mod console;
+
+// settings are needed before window is created
+pub use settings::structures::GameSettings;
 
 /// A main game logic plugin, this plugin
 /// add all systems of game
@@ -24,9 +50,9 @@ pub struct GamePlugin;
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
         // add dev plugins
-        if cfg!(debug_assertions) {
-            app.add_plugins(WorldInspectorPlugin::new());
-            // app.add_plugins(RapierDebugRenderPlugin::default());
+        #[cfg(debug_assertions)] {
+            app.add_plugins((WorldInspectorPlugin::new(), console::ConsolePlugin));
+            app.add_plugins(RapierDebugRenderPlugin::default().disabled());
         }
 
         // add plugins
@@ -36,19 +62,44 @@ impl Plugin for GamePlugin {
             player::PlayerPlugin,
             SkeinPlugin::default(),
             MeshPickingPlugin,
+            hallucination::HallucinationPlugin,
+            audio::GameAudioPlugin,
+            settings::SettingsPlugin,
+            localization::LocalizationPlugin,
+            interaction::InteractionPlugin,
+            ui::UiPlugin,
+            camera::CameraPlugin,
+            cutscene::CutscenePlugin,
+            triggers::TriggersPlugin,
+        ));
+
+        // add game progress plugins
+        app.add_plugins((
+            save::SavePlugin,
+            objectives::ObjectivesPlugin,
+            notes::NotesPlugin,
+            levels::LevelsPlugin,
+            forest::ForestPlugin,
+            terrain::TerrainPlugin,
+            vegetation::VegetationPlugin,
+            atmosphere::AtmospherePlugin,
+            weather::WeatherPlugin,
+            lights::LightsPlugin,
         ));
 
         // register types
         app.register_type::<components::SphereOfTear>();
+        app.register_type::<components::SurfaceMaterial>();
+        app.register_type::<components::Item>();
+        app.register_type::<components::Monster>();
+        app.register_type::<components::Ladder>();
 
         // add resources
 
         // add systems
-        app.add_systems(Startup, (
-            systems::setup_world, ui::setup_gui
-        ));
+        app.add_systems(Startup, systems::setup_world);
 
-        app.add_systems(Update, (ui::update_gui_text, systems::in_inventory));
+        app.add_systems(Update, systems::in_inventory);
     }
 }
 
@@ -0,0 +1,26 @@
+//! Lights - behaviours of point and spot lights: flickering,
+//! pulsing, failing bulbs, lights which go out near the monster
+//! and lights turned on and off by switches. Behaviours can be
+//! set on lights in blender.
+
+// import crates
+use bevy::prelude::*;
+
+// light systems
+pub(super) mod systems;
+pub mod structures;
+
+pub struct LightsPlugin;
+impl Plugin for LightsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<structures::LightBehaviour>();
+        app.register_type::<structures::LightSwitch>();
+
+        app.add_systems(Update, (
+            systems::setup_light_behaviours,
+            systems::setup_light_switches,
+            systems::use_light_switches,
+            systems::update_light_behaviours,
+        ).chain());
+    }
+}
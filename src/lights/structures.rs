@@ -0,0 +1,102 @@
+//! This module store structures of light behaviours
+use bevy::prelude::*;
+use educe::Educe;
+
+use crate::random::SeededRng;
+
+// const's
+/// prompt of light switch
+pub(super) const SWITCH_PROMPT: &str = "interact-switch";
+/// path to sound of light switch
+pub(super) const SWITCH_SOUND: &str = "audio/lights/switch.ogg";
+/// steps of `Sequence` pattern per second
+pub(super) const SEQUENCE_RATE: f32 = 10.0;
+/// how fast light goes out and comes back near the monster (per second)
+pub(super) const MONSTER_FADE_SPEED: f32 = 4.0;
+
+/// Pattern of light's intensity
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum LightPattern {
+    /// intensity doesn't change
+    #[default]
+    Steady,
+    /// random flickering
+    Flicker,
+    /// smooth pulsing
+    Pulse,
+    /// light works, but sometimes flickers and goes out for a moment
+    Failing,
+    /// brightness is set by `sequence` string
+    Sequence,
+}
+
+/// Behaviour of `PointLight` or `SpotLight`, it can be set in blender
+#[derive(Component, Clone, Debug, Educe, Reflect)]
+#[reflect(Component)]
+#[educe(Default)]
+pub struct LightBehaviour {
+    /// pattern of intensity
+    pub pattern: LightPattern,
+
+    #[educe(Default = 1.0)] /// speed of pattern
+    pub speed: f32,
+
+    #[educe(Default = 1.0)] /// how much intensity drops in pattern (0.0 - 1.0)
+    pub depth: f32,
+
+    /// brightness for `Sequence` pattern, one letter per step:
+    /// "a" - dark, "m" - normal, "z" - double (for example "mmamammmmammamamaaamammma")
+    pub sequence: String,
+
+    /// light goes out when monster is closer than this distance, zero - never
+    pub monster_radius: f32,
+
+    /// group of switches which turn light on and off, empty - light hasn't switches
+    pub switch_group: String,
+
+    #[educe(Default = true)] /// light is turned on
+    pub on: bool,
+}
+
+/// Switch which turns lights of group on and off, it can be set in blender
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component)]
+pub struct LightSwitch {
+    /// group of lights (`switch_group` of `LightBehaviour`), it can't be empty
+    pub group: String,
+}
+
+/// State of light with behaviour
+#[derive(Component)]
+pub struct LightState {
+    /// intensity of light set in scene
+    pub base_intensity: f32,
+    /// time of pattern, it's scaled by speed
+    pub time: f32,
+    /// time before next failure of failing light
+    pub next_failure: f32,
+    /// remaining time of current failure
+    pub failure: f32,
+    /// light near the monster: 1.0 - lit, 0.0 - out
+    pub monster_factor: f32,
+    /// seed of light's noise
+    pub seed: u32,
+    pub rng: SeededRng,
+}
+
+impl LightState {
+    /// Create state of light, every light has own random
+    pub fn new(base_intensity: f32, seed: u64) -> Self {
+        let mut rng = SeededRng::new(seed);
+
+        Self {
+            base_intensity,
+            time: rng.range(0.0, 100.0),
+            next_failure: rng.range(1.0, 6.0),
+            failure: 0.0,
+            monster_factor: 1.0,
+            seed: rng.next_u64() as u32,
+            rng,
+        }
+    }
+}
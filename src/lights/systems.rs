@@ -0,0 +1,192 @@
+//! implementation of light systems
+
+// import crates
+use bevy::{audio::PlaybackMode, prelude::*};
+
+// import data from this crate
+use crate::audio::structures::{AudioEmitter, AudioMixer, LoadSound, MixerBus};
+use crate::components::Monster;
+use crate::interaction::{Interactable, Interacted};
+use super::structures::*;
+
+/// remember intensity of lights with behaviour
+pub fn setup_light_behaviours(
+    mut commands: Commands,
+    lights_query: Query<
+        (Entity, Option<&PointLight>, Option<&SpotLight>),
+        (With<LightBehaviour>, Without<LightState>, Or<(With<PointLight>, With<SpotLight>)>)
+    >,
+) {
+    for (entity, point, spot) in lights_query.iter() {
+        let intensity = point.map(|light| light.intensity)
+            .or(spot.map(|light| light.intensity))
+            .unwrap_or_default();
+
+        commands.entity(entity).insert(LightState::new(intensity, entity.to_bits()));
+    }
+}
+
+/// make light switches interactable
+pub fn setup_light_switches(
+    mut commands: Commands,
+    switches_query: Query<Entity, (Added<LightSwitch>, Without<Interactable>)>,
+) {
+    for entity in switches_query.iter() {
+        commands.entity(entity).insert(Interactable { prompt: SWITCH_PROMPT.into() });
+    }
+}
+
+/// turn lights of group on and off by switch
+pub fn use_light_switches(
+    mut commands: Commands,
+    mut interacted: EventReader<Interacted>,
+    mut lights_query: Query<&mut LightBehaviour>,
+    switches_query: Query<(&LightSwitch, &GlobalTransform)>,
+    mixer: Res<AudioMixer>,
+    assets: Res<AssetServer>,
+) {
+    for Interacted(entity) in interacted.read() {
+        let Ok((switch, transform)) = switches_query.get(*entity) else { continue; };
+
+        // empty group means light without switches, so such switch does nothing
+        if switch.group.is_empty() {
+            warn!("light switch {entity} hasn't group");
+            continue;
+        }
+
+        // if any light of group is on, switch turns all of them off
+        let on = !lights_query.iter()
+            .filter(|behaviour| behaviour.switch_group == switch.group)
+            .any(|behaviour| behaviour.on);
+
+        for mut behaviour in lights_query.iter_mut() {
+            if behaviour.switch_group == switch.group {
+                behaviour.on = on;
+            }
+        }
+
+        let Some(sound) = assets.load_sound(SWITCH_SOUND) else { continue; };
+        commands.spawn((
+            #[cfg(debug_assertions)] Name::new("Light switch sound"),
+            Transform::from_translation(transform.translation()),
+            AudioEmitter::new(MixerBus::Sfx, 1.0).sound(
+                sound, PlaybackMode::Despawn, true, &mixer
+            ),
+        ));
+    }
+}
+
+/// change intensity of lights by their behaviours
+pub fn update_light_behaviours(
+    mut lights_query: Query<(
+        &LightBehaviour,
+        &mut LightState,
+        &GlobalTransform,
+        Option<&mut PointLight>,
+        Option<&mut SpotLight>,
+    )>,
+    monsters_query: Query<&GlobalTransform, With<Monster>>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_secs();
+
+    for (behaviour, mut state, transform, point, spot) in lights_query.iter_mut() {
+        state.time += delta * behaviour.speed;
+
+        let mut factor = pattern_factor(behaviour, &mut state, delta);
+
+        // light flickers when monster approaches and goes out when it's close
+        if behaviour.monster_radius > 0.0 {
+            let distance = monsters_query.iter()
+                .map(|monster| monster.translation().distance(transform.translation()))
+                .fold(f32::INFINITY, f32::min);
+
+            let closeness = 1.0 - ((distance - behaviour.monster_radius) / behaviour.monster_radius).clamp(0.0, 1.0);
+            let target = if closeness >= 1.0 { 0.0 } else { 1.0 };
+            state.monster_factor = state.monster_factor.lerp(target, (MONSTER_FADE_SPEED * delta).min(1.0));
+
+            let noise = value_noise(state.time * 20.0, state.seed);
+            factor *= state.monster_factor * (1.0 - closeness * noise);
+        }
+
+        if !behaviour.on {
+            factor = 0.0;
+        }
+
+        let intensity = state.base_intensity * factor;
+
+        if let Some(mut light) = point {
+            if light.intensity != intensity {
+                light.intensity = intensity;
+            }
+        }
+
+        if let Some(mut light) = spot {
+            if light.intensity != intensity {
+                light.intensity = intensity;
+            }
+        }
+    }
+}
+
+// helper functions
+/// Return multiplier of intensity by light's pattern
+#[inline]
+fn pattern_factor(behaviour: &LightBehaviour, state: &mut LightState, delta: f32) -> f32 {
+    let depth = behaviour.depth.clamp(0.0, 1.0);
+
+    match behaviour.pattern {
+        LightPattern::Steady => 1.0,
+        LightPattern::Flicker => 1.0 - depth * value_noise(state.time * 12.0, state.seed),
+        LightPattern::Pulse => 1.0 - depth * (0.5 - 0.5 * (state.time * std::f32::consts::TAU).cos()),
+        LightPattern::Failing => {
+            if state.failure > 0.0 {
+                state.failure -= delta;
+
+                // bulb blinks while it fails
+                let blink = value_noise(state.time * 30.0, state.seed) > 0.5;
+                if blink { 1.0 } else { 1.0 - depth }
+            } else {
+                state.next_failure -= delta * behaviour.speed;
+
+                if state.next_failure <= 0.0 {
+                    state.failure = state.rng.range(0.3, 1.5);
+                    state.next_failure = state.rng.range(2.0, 8.0);
+                }
+
+                1.0
+            }
+        },
+        LightPattern::Sequence => {
+            let sequence = behaviour.sequence.as_bytes();
+            if sequence.is_empty() {
+                return 1.0;
+            }
+
+            let index = (state.time * SEQUENCE_RATE) as usize % sequence.len();
+            let step = sequence[index].to_ascii_lowercase().clamp(b'a', b'z') - b'a';
+            1.0_f32.lerp(step as f32 / 12.0, depth)
+        },
+    }
+}
+
+/// Return smooth noise (0.0 - 1.0)
+#[inline]
+fn value_noise(x: f32, seed: u32) -> f32 {
+    let cell = x.floor();
+    let t = x - cell;
+    let t = t * t * (3.0 - 2.0 * t);
+
+    hash(cell as i32, seed).lerp(hash(cell as i32 + 1, seed), t)
+}
+
+/// Return random value (0.0 - 1.0) of integer
+#[inline]
+fn hash(x: i32, seed: u32) -> f32 {
+    let mut h = (x as u32).wrapping_mul(0x27D4_EB2D) ^ seed;
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x85EB_CA6B);
+    h ^= h >> 13;
+
+    (h & 0xFFFF) as f32 / 65535.0
+}
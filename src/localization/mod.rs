@@ -0,0 +1,26 @@
+//! Localization - in this module located translations of game's
+//! strings. Strings are stored in fluent-like files in `assets/locale/`,
+//! if string doesn't exist in language english string is used.
+
+// import crates
+use bevy::prelude::*;
+
+// localization systems
+pub(super) mod systems;
+pub mod structures;
+
+pub struct LocalizationPlugin;
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(structures::Localization::shipped());
+
+        #[cfg(debug_assertions)]
+        app.add_systems(Startup, systems::check_missing_keys);
+
+        app.add_systems(Update, (
+            systems::sync_language,
+            systems::update_localized_texts,
+            systems::update_localized_spans,
+        ).chain());
+    }
+}
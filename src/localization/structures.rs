@@ -0,0 +1,221 @@
+//! This module store localization structures and parser of string files
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::settings::structures::Language;
+
+/// String files of all shipped languages
+pub(super) const SHIPPED_LOCALES: [(Language, &str); 2] = [
+    (Language::English, include_str!("../../assets/locale/en.ftl")),
+    (Language::Russian, include_str!("../../assets/locale/ru.ftl")),
+];
+
+/// Language which is used if string doesn't exist in current language
+pub(super) const FALLBACK_LANGUAGE: Language = Language::English;
+
+/// Tables of strings for all languages
+#[derive(Resource, Default)]
+pub struct Localization {
+    /// current language
+    pub language: Language,
+    /// strings of languages
+    pub tables: HashMap<Language, HashMap<String, String>>,
+}
+
+impl Localization {
+    /// Create localization with all shipped languages
+    pub fn shipped() -> Self {
+        let mut localization = Self::default();
+
+        for (language, source) in SHIPPED_LOCALES {
+            localization.tables.insert(language, parse_strings(source));
+        }
+
+        localization
+    }
+
+    /// Return string of current language, fallback string
+    /// or the key itself if string doesn't exist anywhere
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.tables.get(&self.language)
+            .and_then(|table| table.get(key))
+            .or_else(|| self.tables.get(&FALLBACK_LANGUAGE).and_then(|table| table.get(key)))
+            .map(String::as_str)
+            .unwrap_or(key)
+    }
+
+    /// Return string with replaced variables (`{ $name }`)
+    pub fn format(&self, key: &str, args: &[(String, String)]) -> String {
+        let mut text = self.get(key).to_string();
+
+        for (name, value) in args {
+            text = text
+                .replace(&format!("{{ ${name} }}"), value)
+                .replace(&format!("{{${name}}}"), value);
+        }
+
+        text
+    }
+
+    /// Return keys of fallback language, which don't exist in other languages
+    pub fn missing_keys(&self) -> Vec<(Language, String)> {
+        let Some(fallback) = self.tables.get(&FALLBACK_LANGUAGE) else { return Vec::new(); };
+        let mut missing = Vec::new();
+
+        for (language, table) in self.tables.iter() {
+            for key in fallback.keys() {
+                if !table.contains_key(key) {
+                    missing.push((*language, key.clone()));
+                }
+            }
+        }
+
+        missing
+    }
+
+    /// Return keys of other languages, which don't exist in fallback language
+    pub fn unknown_keys(&self) -> Vec<(Language, String)> {
+        let Some(fallback) = self.tables.get(&FALLBACK_LANGUAGE) else { return Vec::new(); };
+        let mut unknown = Vec::new();
+
+        for (language, table) in self.tables.iter() {
+            for key in table.keys() {
+                if !fallback.contains_key(key) {
+                    unknown.push((*language, key.clone()));
+                }
+            }
+        }
+
+        unknown
+    }
+}
+
+/// Text which is translated to current language.
+/// It works with `Text` and `TextSpan`.
+#[derive(Component, Clone, Debug, Default)]
+pub struct Localized {
+    /// key of string
+    pub key: String,
+    /// variables of string
+    pub args: Vec<(String, String)>,
+    /// variables, which are keys of other strings (translated with text)
+    pub localized_args: Vec<(String, String)>,
+}
+
+impl Localized {
+    /// Create localized text by key
+    pub fn new(key: impl Into<String>) -> Self {
+        Self { key: key.into(), ..default() }
+    }
+
+    /// Add variable to string
+    pub fn with_arg(mut self, name: impl Into<String>, value: impl ToString) -> Self {
+        self.args.push((name.into(), value.to_string()));
+        self
+    }
+
+    /// Add variable, which is key of other string
+    pub fn with_localized_arg(mut self, name: impl Into<String>, key: impl Into<String>) -> Self {
+        self.localized_args.push((name.into(), key.into()));
+        self
+    }
+
+    /// Return text in current language
+    pub fn translate(&self, localization: &Localization) -> String {
+        let localized_args = self.localized_args.iter()
+            .map(|(name, key)| (name.clone(), localization.get(key).to_string()));
+
+        let args: Vec<_> = self.args.iter().cloned().chain(localized_args).collect();
+        localization.format(&self.key, &args)
+    }
+}
+
+/// Parse string file. It's a small subset of fluent:
+/// `key = value`, indented lines continue value,
+/// `#` starts comment and `{ "text" }` is a string literal.
+pub fn parse_strings(source: &str) -> HashMap<String, String> {
+    let mut table: HashMap<String, String> = HashMap::new();
+    let mut current: Option<String> = None;
+
+    for line in source.lines() {
+        let trimmed = line.trim();
+
+        // comments and empty lines end multiline value
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            current = None;
+            continue;
+        }
+
+        // continuation of multiline value
+        if line.starts_with([' ', '\t']) {
+            if let Some(value) = current.as_ref().and_then(|key| table.get_mut(key)) {
+                if !value.is_empty() {
+                    value.push('\n');
+                }
+                value.push_str(trimmed);
+            }
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once('=') {
+            let key = key.trim().to_string();
+            table.insert(key.clone(), value.trim().to_string());
+            current = Some(key);
+        }
+    }
+
+    for value in table.values_mut() {
+        *value = resolve_literals(value);
+    }
+
+    table
+}
+
+// helper functions
+
+/// Replace string literals (`{ " " }`) with their text
+fn resolve_literals(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}').map(|end| start + end) else { break; };
+        let inner = rest[start + 1..end].trim();
+
+        result.push_str(&rest[..start]);
+        if inner.len() >= 2 && inner.starts_with('"') && inner.ends_with('"') {
+            result.push_str(&inner[1..inner.len() - 1]);
+        } else {
+            // it's a variable, keep it for format
+            result.push_str(&rest[start..=end]);
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shipped_locales_have_all_fallback_keys() {
+        assert_eq!(Localization::shipped().missing_keys(), Vec::new());
+    }
+
+    #[test]
+    fn shipped_locales_have_no_unknown_keys() {
+        assert_eq!(Localization::shipped().unknown_keys(), Vec::new());
+    }
+
+    #[test]
+    fn all_shipped_locales_are_parsed() {
+        let localization = Localization::shipped();
+
+        for (language, _) in SHIPPED_LOCALES {
+            assert!(localization.tables.get(&language).is_some_and(|table| !table.is_empty()));
+        }
+    }
+}
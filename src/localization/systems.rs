@@ -0,0 +1,54 @@
+//! implementation of localization systems
+
+// import crates
+use bevy::prelude::*;
+
+// import data from this crate
+use crate::settings::structures::GameSettings;
+use super::structures::*;
+
+/// warn about strings which don't exist in some languages
+#[cfg(debug_assertions)]
+pub fn check_missing_keys(localization: Res<Localization>) {
+    for (language, key) in localization.missing_keys() {
+        warn!("string \"{key}\" doesn't exist in {language:?} locale");
+    }
+
+    for (language, key) in localization.unknown_keys() {
+        warn!("string \"{key}\" of {language:?} locale doesn't exist in fallback locale");
+    }
+}
+
+/// set language from settings
+pub fn sync_language(
+    mut localization: ResMut<Localization>,
+    settings: Res<GameSettings>,
+) {
+    if settings.is_changed() && localization.language != settings.language {
+        localization.language = settings.language;
+    }
+}
+
+/// translate texts when language or key changed
+pub fn update_localized_texts(
+    mut texts_query: Query<(Ref<Localized>, &mut Text)>,
+    localization: Res<Localization>,
+) {
+    for (localized, mut text) in texts_query.iter_mut() {
+        if localization.is_changed() || localized.is_changed() {
+            **text = localized.translate(&localization);
+        }
+    }
+}
+
+/// translate text spans when language or key changed
+pub fn update_localized_spans(
+    mut spans_query: Query<(Ref<Localized>, &mut TextSpan)>,
+    localization: Res<Localization>,
+) {
+    for (localized, mut span) in spans_query.iter_mut() {
+        if localization.is_changed() || localized.is_changed() {
+            **span = localized.translate(&localization);
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use the_nightmarish_forest::GamePlugin;
+use the_nightmarish_forest::{GamePlugin, GameSettings};
 use autodefault::autodefault;
 use bevy::prelude::*;
 
@@ -7,11 +7,15 @@ fn main() -> AppExit {
     // create app
     let mut app = App::new(); // create app
 
+    // window is created with saved resolution and vsync
+    let settings = GameSettings::load();
+
     // add default plugins and setup window
     app.add_plugins( 
         DefaultPlugins.set(WindowPlugin {
             primary_window: Some(Window {
                 title: "The nightmarish forest".into(),
+                ..settings.window()
             })
         }).set(AssetPlugin {
             watch_for_changes_override: Some(true),
@@ -0,0 +1,33 @@
+//! Notes - readable documents in world. Read note is stored
+//! in journal, where it can be opened again.
+
+// import crates
+use bevy::prelude::*;
+
+use crate::interaction::interaction_systems;
+use crate::save::structures::SaveSet;
+
+// note systems
+pub(super) mod systems;
+pub mod structures;
+
+pub struct NotesPlugin;
+impl Plugin for NotesPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<structures::Note>();
+        app.insert_resource(structures::NoteJournal::default());
+        app.insert_resource(structures::NoteReader::default());
+
+        app.add_systems(Startup, (systems::load_note_fonts, systems::setup_note_reader));
+        app.add_systems(Update, (
+            systems::setup_notes,
+            // closing key must not interact with object behind note
+            systems::close_note_reader.before(interaction_systems::interact),
+            systems::collect_notes,
+            systems::open_journal_notes,
+            systems::update_note_reader,
+            systems::update_journal_notes,
+        ).chain());
+        app.add_systems(Update, systems::sync_notes_save.in_set(SaveSet::Sync));
+    }
+}
@@ -0,0 +1,97 @@
+//! This module store note's structures
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// const's
+/// prompt of note
+pub(super) const NOTE_PROMPT: &str = "interact-read";
+/// time, after which opened note is read (in seconds)
+pub(super) const NOTE_READ_TIME: f32 = 1.5;
+/// path to handwritten font, it's optional
+pub(super) const HANDWRITTEN_FONT: &str = "fonts/handwritten.ttf";
+
+/// Note in world, it's title and text are localized
+/// by keys `note-<id>-title` and `note-<id>-text`
+#[derive(Component, Reflect, Clone, Debug, Default)]
+#[reflect(Component)]
+pub struct Note {
+    /// id of note
+    pub id: String,
+    /// note is written by hand
+    pub handwritten: bool,
+}
+
+impl Note {
+    /// Return key of localized title
+    pub fn title_key(id: &str) -> String {
+        format!("note-{id}-title")
+    }
+
+    /// Return key of localized text
+    pub fn text_key(id: &str) -> String {
+        format!("note-{id}-text")
+    }
+}
+
+/// Note in journal
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct NoteEntry {
+    /// id of note
+    pub id: String,
+    /// note is written by hand
+    pub handwritten: bool,
+    /// note was read
+    pub read: bool,
+}
+
+/// Collected notes
+#[derive(Resource, Default)]
+pub struct NoteJournal {
+    pub entries: Vec<NoteEntry>,
+}
+
+impl NoteJournal {
+    /// Return entry of note
+    pub fn get(&self, id: &str) -> Option<&NoteEntry> {
+        self.entries.iter().find(|entry| entry.id == id)
+    }
+
+    /// Return mutable entry of note
+    pub fn get_mut(&mut self, id: &str) -> Option<&mut NoteEntry> {
+        self.entries.iter_mut().find(|entry| entry.id == id)
+    }
+
+    /// Return count of unread notes
+    pub fn unread(&self) -> usize {
+        self.entries.iter().filter(|entry| !entry.read).count()
+    }
+}
+
+/// State of note reader
+#[derive(Resource, Default)]
+pub struct NoteReader {
+    /// id of opened note
+    pub note: Option<String>,
+    /// time while note is opened
+    pub open_for: f32,
+    /// player's input was enabled before note is opened
+    pub input_was_enabled: bool,
+}
+
+/// Fonts of notes
+#[derive(Resource)]
+pub struct NoteFonts {
+    pub handwritten: Handle<Font>,
+}
+
+#[derive(Component)] /// root of note reader
+pub struct NoteReaderRoot;
+
+#[derive(Component)] /// title of opened note
+pub struct NoteTitleText;
+
+#[derive(Component)] /// text of opened note
+pub struct NoteBodyText;
+
+#[derive(Component)] /// button of note in journal
+pub struct NoteButton(pub String);
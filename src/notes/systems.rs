@@ -0,0 +1,289 @@
+//! implementation of note systems
+
+// import crates
+use bevy::prelude::*;
+
+// import data from this crate
+use crate::interaction::{Interactable, Interacted, INTERACTION_KEY};
+use crate::localization::structures::Localized;
+use crate::objectives::structures::JournalNotes;
+use crate::player::structures::{InputOwner, InputOwners, PlayerInputEnabled};
+use crate::save::structures::{GameLoaded, SaveData, SaveGame};
+use super::structures::*;
+
+/// load optional fonts of notes
+pub fn load_note_fonts(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    commands.insert_resource(NoteFonts {
+        handwritten: asset_server.load(HANDWRITTEN_FONT),
+    });
+}
+
+/// setup paper overlay of note reader
+pub fn setup_note_reader(
+    mut commands: Commands,
+) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+        GlobalZIndex(6),
+        Visibility::Hidden,
+        PickingBehavior::IGNORE,
+        NoteReaderRoot,
+    )).with_children(|parent| {
+        parent.spawn((
+            Node {
+                width: Val::Px(560.0),
+                min_height: Val::Px(640.0),
+                padding: UiRect::all(Val::Px(36.0)),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(18.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.86, 0.82, 0.72)),
+        )).with_children(|parent| {
+            parent.spawn((
+                TextFont { font_size: 30.0, ..default() },
+                TextColor(Color::srgb(0.15, 0.12, 0.1)),
+                Text::new(""),
+                Localized::default(),
+                NoteTitleText,
+            ));
+
+            parent.spawn((
+                TextFont { font_size: 22.0, ..default() },
+                TextColor(Color::srgb(0.2, 0.17, 0.14)),
+                Text::new(""),
+                Localized::default(),
+                NoteBodyText,
+            ));
+        });
+
+        parent.spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(30.0),
+                ..default()
+            },
+            TextFont { font_size: 20.0, ..default() },
+            Text::new(""),
+            Localized::new("note-close"),
+        ));
+    });
+}
+
+/// make notes interactable
+pub fn setup_notes(
+    mut commands: Commands,
+    notes_query: Query<Entity, (Added<Note>, Without<Interactable>)>,
+) {
+    for entity in notes_query.iter() {
+        commands.entity(entity).insert(Interactable { prompt: NOTE_PROMPT.into() });
+    }
+}
+
+/// close note reader by E or right click, note is read
+/// only if it was opened enough time
+pub fn close_note_reader(
+    mut input_enabled: ResMut<PlayerInputEnabled>,
+    mut input_owners: ResMut<InputOwners>,
+    mut journal: ResMut<NoteJournal>,
+    mut reader: ResMut<NoteReader>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+) {
+    let Some(note) = reader.note.clone() else { return; };
+
+    // note is opened in this frame
+    let just_opened = reader.open_for <= 0.0;
+    reader.open_for += time.delta_secs();
+
+    let unread = journal.get(&note).is_some_and(|entry| !entry.read);
+    if unread && reader.open_for >= NOTE_READ_TIME {
+        if let Some(entry) = journal.get_mut(&note) {
+            entry.read = true;
+        }
+    }
+
+    if just_opened {
+        return;
+    }
+
+    if keys.just_pressed(INTERACTION_KEY) || buttons.just_pressed(MouseButton::Right) {
+        reader.note = None;
+        input_enabled.0 = reader.input_was_enabled;
+        input_owners.0.remove(&InputOwner::Note);
+
+        // key closed note, so it must not interact with something in this frame
+        keys.clear_just_pressed(INTERACTION_KEY);
+    }
+}
+
+/// collect notes with which player interacted
+pub fn collect_notes(
+    mut commands: Commands,
+    mut interacted: EventReader<Interacted>,
+    mut input_enabled: ResMut<PlayerInputEnabled>,
+    mut input_owners: ResMut<InputOwners>,
+    mut journal: ResMut<NoteJournal>,
+    mut reader: ResMut<NoteReader>,
+    notes_query: Query<&Note>,
+) {
+    for Interacted(entity) in interacted.read() {
+        let Ok(note) = notes_query.get(*entity) else { continue; };
+
+        if journal.get(&note.id).is_none() {
+            journal.entries.push(NoteEntry {
+                id: note.id.clone(),
+                handwritten: note.handwritten,
+                read: false,
+            });
+        }
+
+        // note is moved to journal
+        commands.entity(*entity).despawn_recursive();
+        open_note(&mut reader, &mut input_enabled, &mut input_owners, &note.id);
+    }
+}
+
+/// open notes by buttons in journal
+pub fn open_journal_notes(
+    buttons_query: Query<(&Interaction, &NoteButton), Changed<Interaction>>,
+    mut input_enabled: ResMut<PlayerInputEnabled>,
+    mut input_owners: ResMut<InputOwners>,
+    mut reader: ResMut<NoteReader>,
+) {
+    for (interaction, button) in buttons_query.iter() {
+        if *interaction == Interaction::Pressed {
+            open_note(&mut reader, &mut input_enabled, &mut input_owners, &button.0);
+        }
+    }
+}
+
+/// show opened note
+pub fn update_note_reader(
+    mut root_query: Query<&mut Visibility, With<NoteReaderRoot>>,
+    mut title_query: Query<(&mut Localized, &mut TextFont), (With<NoteTitleText>, Without<NoteBodyText>)>,
+    mut body_query: Query<(&mut Localized, &mut TextFont), (With<NoteBodyText>, Without<NoteTitleText>)>,
+    asset_server: Res<AssetServer>,
+    journal: Res<NoteJournal>,
+    reader: Res<NoteReader>,
+    fonts: Res<NoteFonts>,
+) {
+    if !reader.is_changed() {
+        return;
+    }
+
+    for mut visibility in root_query.iter_mut() {
+        visibility.set_if_neq(if reader.note.is_some() { Visibility::Inherited } else { Visibility::Hidden });
+    }
+
+    let Some(id) = reader.note.as_deref() else { return; };
+
+    // handwritten font is used only if it exists
+    let handwritten = journal.get(id).is_some_and(|entry| entry.handwritten);
+    let font = if handwritten && asset_server.is_loaded_with_dependencies(&fonts.handwritten) {
+        fonts.handwritten.clone()
+    } else {
+        Handle::default()
+    };
+
+    for (mut localized, mut text_font) in title_query.iter_mut() {
+        if localized.key != Note::title_key(id) {
+            *localized = Localized::new(Note::title_key(id));
+        }
+        if text_font.font != font {
+            text_font.font = font.clone();
+        }
+    }
+
+    for (mut localized, mut text_font) in body_query.iter_mut() {
+        if localized.key != Note::text_key(id) {
+            *localized = Localized::new(Note::text_key(id));
+        }
+        if text_font.font != font {
+            text_font.font = font.clone();
+        }
+    }
+}
+
+/// rebuild list of notes in journal, unread notes are marked
+pub fn update_journal_notes(
+    mut commands: Commands,
+    list_query: Query<Entity, With<JournalNotes>>,
+    journal: Res<NoteJournal>,
+) {
+    if !journal.is_changed() {
+        return;
+    }
+
+    for list in list_query.iter() {
+        commands.entity(list).despawn_descendants().with_children(|parent| {
+            parent.spawn((
+                TextFont { font_size: 28.0, ..default() },
+                Text::new(""),
+                Localized::new("journal-notes").with_arg("unread", journal.unread()),
+            ));
+
+            for entry in journal.entries.iter() {
+                let color = if entry.read { Color::srgb(0.6, 0.58, 0.55) } else { Color::srgb(0.95, 0.85, 0.5) };
+
+                parent.spawn((
+                    Button,
+                    Node { padding: UiRect::axes(Val::Px(6.0), Val::Px(2.0)), ..default() },
+                    NoteButton(entry.id.clone()),
+                )).with_child((
+                    TextFont { font_size: 20.0, ..default() },
+                    TextColor(color),
+                    Text::new(""),
+                    Localized::new(Note::title_key(&entry.id)),
+                ));
+            }
+        });
+    }
+}
+
+/// write notes to save and apply them after loading
+pub fn sync_notes_save(
+    mut journal: ResMut<NoteJournal>,
+    mut save: EventReader<SaveGame>,
+    mut loaded: EventReader<GameLoaded>,
+    mut data: ResMut<SaveData>,
+) {
+    if save.read().count() > 0 {
+        data.notes = journal.entries.clone();
+    }
+
+    if loaded.read().count() > 0 {
+        journal.entries = data.notes.clone();
+    }
+}
+
+// helper functions
+/// open note in reader
+#[inline]
+fn open_note(
+    reader: &mut NoteReader,
+    input_enabled: &mut PlayerInputEnabled,
+    input_owners: &mut InputOwners,
+    id: &str,
+) {
+    if reader.note.is_none() {
+        reader.input_was_enabled = input_enabled.0;
+    }
+
+    reader.note = Some(id.to_string());
+    reader.open_for = 0.0;
+    input_enabled.0 = false;
+    input_owners.0.insert(InputOwner::Note);
+}
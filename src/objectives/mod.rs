@@ -0,0 +1,37 @@
+//! Objectives - quest steps defined in data (`assets/objectives/`)
+//! with prerequisites and completion conditions, on-screen tracker
+//! and journal (opened by J or from inventory)
+
+// import crates
+use bevy::prelude::*;
+
+use crate::components::HotkeySystems;
+use crate::ron_loader::RonLoader;
+use crate::save::structures::SaveSet;
+
+// objective systems
+pub(super) mod systems;
+pub mod structures;
+
+pub struct ObjectivesPlugin;
+impl Plugin for ObjectivesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<structures::ObjectiveSet>();
+        app.register_asset_loader(RonLoader::<structures::ObjectiveSet>::new(&["objectives.ron"]));
+
+        app.add_event::<structures::PuzzleSolved>();
+        app.add_event::<structures::ObjectiveCompleted>();
+        app.insert_resource(structures::PendingConditions::default());
+        app.add_event::<structures::OpenJournal>();
+        app.insert_resource(structures::JournalState::default());
+
+        app.add_systems(Startup, (systems::load_objectives, systems::setup_objectives_ui));
+        app.add_systems(Update, (
+            systems::complete_objectives,
+            systems::update_objective_states,
+            systems::toggle_journal.in_set(HotkeySystems),
+            systems::update_objectives_ui,
+        ).chain());
+        app.add_systems(Update, systems::sync_objectives_save.in_set(SaveSet::Sync));
+    }
+}
@@ -0,0 +1,108 @@
+//! This module store objective's structures and data
+use bevy::{prelude::*, utils::HashMap};
+use serde::{Deserialize, Serialize};
+
+use crate::player::structures::SavedInput;
+
+/// Condition of objective's completion
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub enum ObjectiveCondition {
+    /// item with name (key) is collected
+    ItemCollected(String),
+    /// player entered trigger with action
+    TriggerEntered(String),
+    /// puzzle with id is solved
+    PuzzleSolved(String),
+}
+
+/// Definition of objective
+#[derive(Clone, Debug, Deserialize)]
+pub struct ObjectiveDef {
+    /// id of objective
+    pub id: String,
+    /// key of localized title
+    pub title: String,
+    /// ids of objectives, which must be completed before
+    #[serde(default)]
+    pub prerequisites: Vec<String>,
+    /// condition of completion
+    pub condition: ObjectiveCondition,
+}
+
+/// Set of objectives, it's loaded from `*.objectives.ron`
+#[derive(Asset, TypePath, Clone, Debug, Deserialize)]
+pub struct ObjectiveSet {
+    pub objectives: Vec<ObjectiveDef>,
+}
+
+/// Status of objective
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ObjectiveStatus {
+    /// prerequisites aren't completed
+    #[default]
+    Locked,
+    /// player must complete it now
+    Active,
+    /// objective is completed
+    Completed,
+}
+
+/// State of all objectives
+#[derive(Resource)]
+pub struct Objectives {
+    /// data of objectives
+    pub set: Handle<ObjectiveSet>,
+    /// statuses of objectives by id
+    pub statuses: HashMap<String, ObjectiveStatus>,
+}
+
+impl Objectives {
+    /// Return status of objective
+    pub fn status(&self, id: &str) -> ObjectiveStatus {
+        self.statuses.get(id).copied().unwrap_or_default()
+    }
+
+    /// Return true if all prerequisites of objective are completed
+    pub fn prerequisites_completed(&self, objective: &ObjectiveDef) -> bool {
+        objective.prerequisites.iter()
+            .all(|id| self.status(id) == ObjectiveStatus::Completed)
+    }
+}
+
+/// Conditions which are met, but objectives with them
+/// aren't loaded yet or are still locked
+#[derive(Resource, Default)]
+pub struct PendingConditions(pub Vec<ObjectiveCondition>);
+
+/// Event: puzzle is solved
+#[derive(Event, Clone, Debug)]
+pub struct PuzzleSolved(pub String);
+
+/// Event: objective is completed
+#[derive(Event, Clone, Debug)]
+pub struct ObjectiveCompleted(pub String);
+
+/// State of journal
+#[derive(Resource, Default)]
+pub struct JournalState {
+    /// journal is open
+    pub open: bool,
+    /// input and cursor before journal opened
+    pub saved: SavedInput,
+}
+
+/// Event for open journal (for example from inventory)
+#[derive(Event, Clone, Copy, Debug)]
+pub struct OpenJournal;
+
+#[derive(Component)] /// list of active objectives on screen
+pub struct ObjectiveTracker;
+
+#[derive(Component)] /// root of journal
+pub struct Journal;
+
+#[derive(Component)] /// list of objectives in journal
+pub struct JournalObjectives;
+
+#[derive(Component)] /// list of notes in journal
+pub struct JournalNotes;
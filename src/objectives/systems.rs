@@ -0,0 +1,287 @@
+//! implementation of objective systems
+
+// import crates
+use bevy::{prelude::*, window::PrimaryWindow};
+
+// import data from this crate
+use crate::components::{InInventory, Item};
+use crate::interaction::ItemPickedUp;
+use crate::localization::structures::Localized;
+use crate::player::structures::{InputOwner, InputOwners, PlayerInputEnabled, SavedInput};
+use crate::save::structures::{GameLoaded, SaveData, SaveGame};
+use crate::triggers::structures::TriggerEntered;
+use super::structures::*;
+
+/// load objectives of game
+pub fn load_objectives(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    commands.insert_resource(Objectives {
+        set: asset_server.load("objectives/main.objectives.ron"),
+        statuses: default(),
+    });
+}
+
+/// setup on-screen objective tracker and journal
+pub fn setup_objectives_ui(
+    mut commands: Commands,
+) {
+    // add tracker of active objectives
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(20.0),
+            left: Val::Px(20.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(4.0),
+            ..default()
+        },
+        PickingBehavior::IGNORE,
+        ObjectiveTracker,
+    ));
+
+    // add journal
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        GlobalZIndex(5),
+        Visibility::Hidden,
+        Journal,
+    )).with_children(|parent| {
+        parent.spawn((
+            Node {
+                padding: UiRect::all(Val::Px(20.0)),
+                column_gap: Val::Px(40.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.12, 0.11, 0.1)),
+        )).with_children(|parent| {
+            parent.spawn((
+                Node {
+                    width: Val::Px(420.0),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(6.0),
+                    ..default()
+                },
+                JournalObjectives,
+            ));
+
+            parent.spawn((
+                Node {
+                    width: Val::Px(300.0),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(6.0),
+                    ..default()
+                },
+                JournalNotes,
+            ));
+        });
+    });
+}
+
+/// complete active objectives, whose conditions are met. Conditions are
+/// remembered while objectives aren't loaded or are still locked
+pub fn complete_objectives(
+    mut objectives: Option<ResMut<Objectives>>,
+    mut pending: ResMut<PendingConditions>,
+    mut completed: EventWriter<ObjectiveCompleted>,
+    mut picked: EventReader<ItemPickedUp>,
+    mut entered: EventReader<TriggerEntered>,
+    mut solved: EventReader<PuzzleSolved>,
+    sets: Res<Assets<ObjectiveSet>>,
+) {
+    // collect conditions met in this frame
+    pending.0.extend(picked.read().map(|item| ObjectiveCondition::ItemCollected(item.name.clone())));
+    pending.0.extend(entered.read().map(|trigger| ObjectiveCondition::TriggerEntered(trigger.action.clone())));
+    pending.0.extend(solved.read().map(|puzzle| ObjectiveCondition::PuzzleSolved(puzzle.0.clone())));
+
+    if pending.0.is_empty() {
+        return;
+    }
+
+    let Some(objectives) = objectives.as_mut() else { return; };
+    let Some(set) = sets.get(&objectives.set) else { return; };
+
+    for condition in std::mem::take(&mut pending.0) {
+        let mut locked = false;
+
+        for objective in set.objectives.iter().filter(|objective| objective.condition == condition) {
+            match objectives.status(&objective.id) {
+                ObjectiveStatus::Active => {
+                    objectives.statuses.insert(objective.id.clone(), ObjectiveStatus::Completed);
+                    completed.send(ObjectiveCompleted(objective.id.clone()));
+                },
+                ObjectiveStatus::Locked => locked = true,
+                ObjectiveStatus::Completed => {},
+            }
+        }
+
+        // objective is completed when it's unlocked
+        if locked && !pending.0.contains(&condition) {
+            pending.0.push(condition);
+        }
+    }
+}
+
+/// activate objectives, whose prerequisites are completed
+pub fn update_objective_states(
+    mut objectives: Option<ResMut<Objectives>>,
+    mut completed: EventWriter<ObjectiveCompleted>,
+    items_query: Query<&Item, With<InInventory>>,
+    sets: Res<Assets<ObjectiveSet>>,
+) {
+    let Some(objectives) = objectives.as_mut() else { return; };
+    let Some(set) = sets.get(&objectives.set) else { return; };
+
+    // one objective can unlock others, so repeat until nothing changes
+    let mut changed = true;
+    while changed {
+        changed = false;
+
+        for objective in set.objectives.iter() {
+            if objectives.status(&objective.id) != ObjectiveStatus::Locked
+                || !objectives.prerequisites_completed(objective)
+            {
+                continue;
+            }
+
+            // item can be already collected
+            let collected = match &objective.condition {
+                ObjectiveCondition::ItemCollected(name) => items_query.iter().any(|item| &item.name == name),
+                _ => false,
+            };
+
+            if collected {
+                objectives.statuses.insert(objective.id.clone(), ObjectiveStatus::Completed);
+                completed.send(ObjectiveCompleted(objective.id.clone()));
+            } else {
+                objectives.statuses.insert(objective.id.clone(), ObjectiveStatus::Active);
+            }
+
+            changed = true;
+        }
+    }
+}
+
+/// open and close journal by J or from inventory,
+/// while journal is open cursor is free and player's input is disabled
+pub fn toggle_journal(
+    mut journal_query: Query<&mut Visibility, With<Journal>>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+    mut input_enabled: ResMut<PlayerInputEnabled>,
+    mut journal: ResMut<JournalState>,
+    mut input_owners: ResMut<InputOwners>,
+    mut requests: EventReader<OpenJournal>,
+    keys: Res<ButtonInput<KeyCode>>,
+) {
+    let requested = requests.read().count() > 0;
+
+    let open = if keys.just_pressed(KeyCode::KeyJ) {
+        // by J journal is opened only from game, else other screen owns input
+        !journal.open && input_enabled.0
+    } else if requested {
+        true
+    } else {
+        return;
+    };
+
+    if open == journal.open {
+        return;
+    }
+
+    journal.open = open;
+    if open {
+        journal.saved = SavedInput::take(&mut input_enabled, window_query.get_single_mut().ok());
+        input_owners.0.insert(InputOwner::Journal);
+    } else {
+        journal.saved.restore(&mut input_enabled, window_query.get_single_mut().ok());
+        input_owners.0.remove(&InputOwner::Journal);
+    }
+
+    for mut visibility in journal_query.iter_mut() {
+        *visibility = if open { Visibility::Inherited } else { Visibility::Hidden };
+    }
+}
+
+/// rebuild objective tracker and journal when objectives are changed
+pub fn update_objectives_ui(
+    mut commands: Commands,
+    tracker_query: Query<Entity, With<ObjectiveTracker>>,
+    journal_query: Query<Entity, With<JournalObjectives>>,
+    objectives: Option<Res<Objectives>>,
+    sets: Res<Assets<ObjectiveSet>>,
+) {
+    let Some(objectives) = objectives else { return; };
+    if !objectives.is_changed() {
+        return;
+    }
+
+    let Some(set) = sets.get(&objectives.set) else { return; };
+
+    let with_status = |status| set.objectives.iter()
+        .filter(move |objective| objectives.status(&objective.id) == status);
+
+    // tracker shows only active objectives
+    for tracker in tracker_query.iter() {
+        commands.entity(tracker).despawn_descendants().with_children(|parent| {
+            for objective in with_status(ObjectiveStatus::Active) {
+                parent.spawn((
+                    TextFont { font_size: 20.0, ..default() },
+                    Text::new(""),
+                    Localized::new(objective.title.clone()),
+                ));
+            }
+        });
+    }
+
+    // journal shows active and completed objectives
+    for journal in journal_query.iter() {
+        commands.entity(journal).despawn_descendants().with_children(|parent| {
+            for (header, status, color) in [
+                ("journal-active", ObjectiveStatus::Active, Color::srgb(0.9, 0.9, 0.85)),
+                ("journal-completed", ObjectiveStatus::Completed, Color::srgb(0.5, 0.5, 0.48)),
+            ] {
+                parent.spawn((
+                    TextFont { font_size: 28.0, ..default() },
+                    Text::new(""),
+                    Localized::new(header),
+                ));
+
+                for objective in with_status(status) {
+                    parent.spawn((
+                        TextFont { font_size: 20.0, ..default() },
+                        TextColor(color),
+                        Text::new(""),
+                        Localized::new(objective.title.clone()),
+                    ));
+                }
+            }
+        });
+    }
+}
+
+/// write objectives to save and apply them after loading
+pub fn sync_objectives_save(
+    mut objectives: Option<ResMut<Objectives>>,
+    mut save: EventReader<SaveGame>,
+    mut loaded: EventReader<GameLoaded>,
+    mut data: ResMut<SaveData>,
+) {
+    let Some(objectives) = objectives.as_mut() else { return; };
+
+    if save.read().count() > 0 {
+        data.objectives = objectives.statuses.clone();
+    }
+
+    if loaded.read().count() > 0 {
+        objectives.statuses = data.objectives.clone();
+    }
+}
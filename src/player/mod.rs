@@ -11,14 +11,21 @@ pub mod structures;
 pub struct PlayerPlugin;
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
+        app.register_type::<structures::MovementSettings>();
+        app.insert_resource(structures::MovementSettings::default());
         app.insert_resource(structures::PlayersInput::default());
         app.insert_resource(structures::PlayerInputEnabled(false));
+        app.insert_resource(structures::InputOwners::default());
+        app.add_event::<structures::PlayerLanded>();
 
         app.add_systems(Startup, systems::setup);
         app.add_systems(Update, (
            systems::update_input, systems::update_cursor_visible, systems::update_fear,
-           systems::update_stamina,
-           (systems::move_character, systems::update_rotation, systems::update_cursor_position).chain() 
+           systems::update_stamina, systems::update_flashlight,
+           (
+               systems::move_character, systems::update_ground_surface,
+               systems::update_rotation, systems::update_cursor_position,
+           ).chain()
         ));
     }
 }
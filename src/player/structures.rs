@@ -1,27 +1,73 @@
 //! This module store player's structures, enums and consts
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashSet, window::CursorGrabMode};
+use bevy_rapier3d::prelude::*;
 use educe::Educe;
 
+use crate::components::SurfaceType;
+
 // const's
-// player size
+// player size (height is half of capsule's height)
 pub(super) const PLAYER_HEIGHT: f32 = 1.5;
+pub(super) const PLAYER_FULL_HEIGHT: f32 = PLAYER_HEIGHT * 2.0;
+pub(super) const PLAYER_CROUCH_HEIGHT: f32 = 1.05;
 pub(super) const PLAYER_RADIUS: f32 = 1.0;
+pub(super) const CROUCH_TRANSITION_SPEED: f32 = 3.0;
+pub(super) const HEADROOM_PROBE_RADIUS: f32 = PLAYER_RADIUS * 0.9;
 
 // player stamina
 pub(super) const MIN_STAMINA_TO_UNBLOCK_RUN: f32 = 52.0;
 pub(super) const STAMINA_RECOVERY_SPEED: f32 = 0.3;
 pub(super) const STAMINA_DECRASE_RATE: f32 = 0.7;
-pub(super) const MAX_STAMINA: f32 = 250.0 * 10000.0;
+pub(crate) const MAX_STAMINA: f32 = 250.0 * 10000.0;
 
 // player speed
 pub(super) const CROUCH_SPEED: f32 = 0.7;
 pub(super) const WALK_SPEED: f32 = 2.0;
 pub(super) const RUN_SPEED: f32 = 4.72;
 
+// minimal part of acceleration near target speed
+pub(super) const MIN_ACCELERATION_SCALE: f32 = 0.2;
+
+// slopes steeper than this angle player can't climb and slides down
+pub(super) const STEEP_SLOPE_ANGLE: f32 = std::f32::consts::FRAC_PI_4;
+
+// dynamic bodies push player if they touch him
+pub(super) const DYNAMIC_PUSH_MARGIN: f32 = 0.1;
+
+// player's speed multiplier in noclip mode
+pub(super) const NOCLIP_SPEED_SCALE: f32 = 3.0;
+
+// player steps on obstacles lower than this height
+pub(super) const AUTOSTEP_HEIGHT: f32 = 1.65;
+
+// ladders, exit heights are from player's feet
+pub(super) const LADDER_GRAB_FACING: f32 = 0.5;
+pub(super) const LADDER_ENTER_TIME: f32 = 0.35;
+pub(super) const LADDER_EXIT_TIME: f32 = 0.7;
+pub(super) const LADDER_EXIT_REACH: f32 = PLAYER_RADIUS * 2.0 + 0.1;
+pub(super) const LADDER_JUMP_OFF_TIME: f32 = 0.2;
+pub(super) const LADDER_JUMP_OFF_DISTANCE: f32 = 0.6;
+pub(super) const LADDER_EXIT_MIN_HEIGHT: f32 = -0.6;
+pub(super) const LADDER_EXIT_MAX_HEIGHT: f32 = 2.4;
+
+// mantling over waist-high obstacles, lower ones are autostepped.
+// Heights are from player's feet, autostep ends at waist (0.55 of
+// full height) and mantling ends a bit higher
+pub(super) const MANTLE_MIN_HEIGHT: f32 = AUTOSTEP_HEIGHT;
+pub(super) const MANTLE_MAX_HEIGHT: f32 = PLAYER_FULL_HEIGHT * 0.7;
+pub(super) const MANTLE_REACH: f32 = 0.6;
+pub(super) const MANTLE_TIME: f32 = 0.6;
+pub(super) const MANTLE_PROBE_HALF_HEIGHT: f32 = 0.1;
+pub(super) const LEDGE_CLEARANCE: f32 = 0.05;
+
 // player's fear
 pub(super) const FEAR_RECOVERY_SPEED: f32 = 0.05;
 pub(super) const FEAR_DECRASE_RATE: f32 = 0.12;
 
+// player's flashlight
+pub(super) const FLASHLIGHT_INTENSITY: f32 = 2_000_000.0;
+pub(super) const FLASHLIGHT_DRAIN_RATE: f32 = 0.004;
+
 // camera translation offsets
 pub(crate) const CAMERA_WALK_TRANSLATION: Vec3 = Vec3::new(0.0, 1.5, 0.0);
 pub(crate) const CAMERA_CROUCH_TRANSLATION: Vec3 = Vec3::ZERO;
@@ -40,6 +86,21 @@ pub struct PlayerComponent {
 
     #[educe(Default = false)] /// if stop_run == true player can't run
     pub stop_run: bool,
+
+    #[educe(Default = false)] /// if fear_frozen == true fear doesn't change
+    pub fear_frozen: bool,
+}
+
+/// Player's flashlight, it's attached to camera
+#[derive(Component, Debug, Educe)]
+#[require(SpotLight)]
+#[educe(Default)]
+pub struct Flashlight {
+    #[educe(Default = false)] /// flashlight is turned on
+    pub on: bool,
+
+    #[educe(Default = 1.0)] /// charge of battery, from 0.0 to 1.0
+    pub battery: f32,
 }
 
 #[derive(Component, Default)] /// player's camera pivot
@@ -48,6 +109,57 @@ pub struct PlayerCameraPivot;
 #[derive(Resource)] /// check player input or not
 pub struct PlayerInputEnabled(pub bool);
 
+/// Who took player's input away, while anyone owns
+/// input right click doesn't grab cursor and enable input
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InputOwner {
+    Cutscene,
+    Console,
+    Settings,
+    Journal,
+    Inventory,
+    Note,
+}
+
+#[derive(Resource, Default, Debug)] /// current owners of player's input
+pub struct InputOwners(pub HashSet<InputOwner>);
+
+/// Player's input and cursor, they are saved when some screen
+/// (menu, journal, inventory) takes them and restored when it's closed
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SavedInput {
+    /// player's input was enabled
+    pub input_enabled: bool,
+    /// cursor was grabbed
+    pub cursor_grabbed: bool,
+}
+
+impl SavedInput {
+    /// Save input and cursor, then disable input and free cursor
+    pub fn take(input_enabled: &mut PlayerInputEnabled, window: Option<Mut<Window>>) -> Self {
+        let mut saved = Self { input_enabled: input_enabled.0, cursor_grabbed: false };
+        input_enabled.0 = false;
+
+        if let Some(mut window) = window {
+            saved.cursor_grabbed = window.cursor_options.grab_mode != CursorGrabMode::None;
+            window.cursor_options.grab_mode = CursorGrabMode::None;
+            window.cursor_options.visible = true;
+        }
+
+        saved
+    }
+
+    /// Restore saved input and cursor
+    pub fn restore(&self, input_enabled: &mut PlayerInputEnabled, window: Option<Mut<Window>>) {
+        input_enabled.0 = self.input_enabled;
+
+        if let Some(mut window) = window.filter(|_| self.cursor_grabbed) {
+            window.cursor_options.grab_mode = CursorGrabMode::Confined;
+            window.cursor_options.visible = false;
+        }
+    }
+}
+
 #[derive(Resource, Default)] /// player's input data
 pub struct PlayersInput {
     // directional
@@ -58,8 +170,104 @@ pub struct PlayersInput {
 
     // action's
     pub jump: bool,
+    pub jump_pressed: bool,
     pub run: bool,
     pub crouch: bool,
+
+    // lean around corners
+    pub lean_left: bool,
+    pub lean_right: bool,
+}
+
+/// Settings of player's movement
+#[derive(Resource, Clone, Debug, Educe, Reflect)]
+#[reflect(Resource)]
+#[educe(Default)]
+pub struct MovementSettings {
+    #[educe(Default = 20.0)] /// acceleration on ground (m/s²)
+    pub ground_acceleration: f32,
+
+    #[educe(Default = 25.0)] /// deceleration on ground when player stops or turns (m/s²)
+    pub ground_deceleration: f32,
+
+    #[educe(Default = 0.5)]
+    /// how much acceleration falls while speed comes to target,
+    /// zero - acceleration is constant
+    pub acceleration_curve: f32,
+
+    #[educe(Default = 0.3)] /// part of ground acceleration in air
+    pub air_control: f32,
+
+    #[educe(Default = 1.0)] /// deceleration in air (m/s²)
+    pub air_deceleration: f32,
+
+    #[educe(Default = 0.8)] /// time of speed up from walk to run (in seconds)
+    pub sprint_ramp_up: f32,
+
+    #[educe(Default = 0.3)] /// time of slow down from run to walk (in seconds)
+    pub sprint_ramp_down: f32,
+
+    #[educe(Default = 0.15)] /// time after leaving ground in which player still can jump (in seconds)
+    pub coyote_time: f32,
+
+    #[educe(Default = 0.15)] /// time before landing in which pressed jump is remembered (in seconds)
+    pub jump_buffer_time: f32,
+}
+
+/// Event: player landed on ground after fall
+#[derive(Event, Clone, Copy, Debug)]
+pub struct PlayerLanded {
+    /// vertical speed of fall (positive)
+    pub fall_speed: f32,
+}
+
+/// Point of player's feet on moving platform
+#[derive(Clone, Copy, Debug)]
+pub struct PlatformAnchor {
+    /// entity of platform's rigid body
+    pub entity: Entity,
+    /// point in platform's local space
+    pub local: Vec3,
+}
+
+/// Animated move of player from one point to other
+/// (enter and exit of ladder, mantling)
+#[derive(Clone, Copy, Debug)]
+pub struct ControllerTransition {
+    pub from: Vec3,
+    pub to: Vec3,
+    /// part of time in which player rises, before it player moves forward slowly.
+    /// Zero - player moves in straight line
+    pub rise: f32,
+    /// progress of move (0.0 - 1.0)
+    pub progress: f32,
+    /// duration of move (in seconds)
+    pub duration: f32,
+}
+
+impl ControllerTransition {
+    /// Create transition from point to other point
+    pub fn new(from: Vec3, to: Vec3, duration: f32, rise: f32) -> Self {
+        Self { from, to, rise, progress: 0.0, duration }
+    }
+
+    /// Return position of player at current progress
+    pub fn position(&self) -> Vec3 {
+        let t = self.progress.clamp(0.0, 1.0);
+        let smooth = |t: f32| t * t * (3.0 - 2.0 * t);
+
+        if self.rise <= 0.0 {
+            return self.from.lerp(self.to, smooth(t));
+        }
+
+        // rise first, then move over the ledge
+        let vertical = smooth((t / self.rise).min(1.0));
+        let horizontal = smooth(((t - self.rise * 0.5) / (1.0 - self.rise * 0.5)).clamp(0.0, 1.0));
+
+        let mut position = self.from.lerp(self.to, horizontal);
+        position.y = self.from.y.lerp(self.to.y, vertical);
+        position
+    }
 }
 
 #[derive(Component, Educe, Debug)]
@@ -71,6 +279,9 @@ pub struct PlayerControllerData {
     #[educe(Default = false)] /// player's crouch in the last frame
     pub crouched: bool,
 
+    #[educe(Default = PLAYER_HEIGHT)] /// half of current player's height, it's smaller while crouch
+    pub height: f32,
+
     // physics data
     #[educe(Default = Vec3::ZERO)] /// player's acceleration
     pub acceleration: Vec3,
@@ -92,5 +303,127 @@ pub struct PlayerControllerData {
 
     #[educe(Default = false)] /// player on ground, or not
     pub grounded: bool,
+
+    #[educe(Default = None)] /// collider under the player
+    pub ground: Option<Entity>,
+
+    #[educe(Default = SurfaceType::Default)] /// surface under the player
+    pub surface: SurfaceType,
+
+    #[educe(Default = Vec3::Y)] /// normal of ground under the player
+    pub ground_normal: Vec3,
+
+    #[educe(Default = false)] /// player slides down steep slope
+    pub sliding: bool,
+
+    #[educe(Default = None)] /// moving platform under the player
+    pub platform: Option<PlatformAnchor>,
+
+    #[educe(Default = Vec3::ZERO)] /// velocity of platform under the player
+    pub platform_velocity: Vec3,
+
+    #[educe(Default = 0.0)] /// progress of sprint's speed up (0.0 - 1.0)
+    pub sprint: f32,
+
+    #[educe(Default = 0.0)] /// remaining time in which player can jump after leaving ground
+    pub coyote: f32,
+
+    #[educe(Default = 0.0)] /// remaining time of remembered jump
+    pub jump_buffer: f32,
+
+    #[educe(Default = 0.0)] /// distance travelled on ground since last step
+    pub travelled: f32,
+
+    #[educe(Default = false)] /// player flies through geometry
+    pub noclip: bool,
+
+    #[educe(Default = false)] /// controller doesn't move (level is loading)
+    pub frozen: bool,
+
+    #[educe(Default = None)] /// ladder on which player climbs
+    pub ladder: Option<Entity>,
+
+    #[educe(Default = None)] /// player is moved by animation
+    pub transition: Option<ControllerTransition>,
+}
+
+/// Return ball, which is cast up to find ceiling before standing up, and it's
+/// offset from player's center. Ball is inside of player's top and narrower than
+/// him, so it doesn't touch walls and floor, but top of ball is top of player
+pub fn headroom_probe(height: f32) -> (Vec3, Collider) {
+    (Vec3::Y * (height - HEADROOM_PROBE_RADIUS), Collider::ball(HEADROOM_PROBE_RADIUS))
 }
 
+/// Return height, to which player can stand up from `height` to `target_height`.
+/// `ceiling` is distance, on which headroom probe cast up hits ceiling, feet stay
+/// on place, so top rises twice faster than center. Zero distance means player
+/// already touches ceiling
+pub fn headroom_height(height: f32, target_height: f32, ceiling: Option<f32>) -> f32 {
+    match ceiling {
+        Some(distance) if target_height > height => {
+            target_height.min(height + (distance / 2.0 - LEDGE_CLEARANCE).max(0.0))
+        },
+        _ => target_height,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_rapier3d::{na::{Isometry3, Vector3}, parry::query};
+
+    use super::*;
+
+    #[test]
+    fn player_stands_up_under_open_sky() {
+        assert_eq!(headroom_height(PLAYER_CROUCH_HEIGHT, PLAYER_HEIGHT, None), PLAYER_HEIGHT);
+    }
+
+    #[test]
+    fn player_stays_crouched_under_low_ceiling() {
+        assert_eq!(headroom_height(PLAYER_CROUCH_HEIGHT, PLAYER_HEIGHT, Some(LEDGE_CLEARANCE)), PLAYER_CROUCH_HEIGHT);
+    }
+
+    #[test]
+    fn player_rises_partially_under_ceiling() {
+        let height = headroom_height(PLAYER_CROUCH_HEIGHT, PLAYER_HEIGHT, Some(0.4));
+
+        assert!(height > PLAYER_CROUCH_HEIGHT && height < PLAYER_HEIGHT);
+        assert!((height - (PLAYER_CROUCH_HEIGHT + 0.2 - LEDGE_CLEARANCE)).abs() < 1e-5);
+    }
+
+    #[test]
+    fn player_touching_ceiling_stays_crouched() {
+        assert_eq!(headroom_height(PLAYER_CROUCH_HEIGHT, PLAYER_HEIGHT, Some(0.0)), PLAYER_CROUCH_HEIGHT);
+    }
+
+    /// cast headroom probe of crouched player standing in origin
+    /// against obstacle, return height to which player stands up
+    fn stand_up_near(obstacle: &Collider, position: Vec3) -> f32 {
+        let (offset, probe) = headroom_probe(PLAYER_CROUCH_HEIGHT);
+        let hit = query::cast_shapes(
+            &Isometry3::translation(offset.x, offset.y, offset.z), &Vector3::y(), &*probe.raw,
+            &Isometry3::translation(position.x, position.y, position.z), &Vector3::zeros(), &*obstacle.raw,
+            query::ShapeCastOptions::with_max_time_of_impact((PLAYER_HEIGHT - PLAYER_CROUCH_HEIGHT) * 2.0),
+        ).expect("shapes are supported");
+
+        headroom_height(PLAYER_CROUCH_HEIGHT, PLAYER_HEIGHT, hit.map(|hit| hit.time_of_impact))
+    }
+
+    #[test]
+    fn player_stays_crouched_under_low_ceiling_collider() {
+        // bottom of ceiling is a bit above crouched player
+        let ceiling = Collider::cuboid(5.0, 0.5, 5.0);
+        let height = stand_up_near(&ceiling, Vec3::Y * (PLAYER_CROUCH_HEIGHT + LEDGE_CLEARANCE + 0.5));
+
+        assert_eq!(height, PLAYER_CROUCH_HEIGHT);
+    }
+
+    #[test]
+    fn player_stands_up_beside_wall_collider() {
+        // wall touches side of player
+        let wall = Collider::cuboid(0.5, 5.0, 5.0);
+        let height = stand_up_near(&wall, Vec3::X * (PLAYER_RADIUS + 0.5));
+
+        assert_eq!(height, PLAYER_HEIGHT);
+    }
+}
@@ -1,5 +1,5 @@
 //! implementation of player systems
-use std::f32::consts::{FRAC_PI_2, PI};
+use std::{f32::consts::{FRAC_PI_2, PI}, ops::Range};
 
 // import crates
 use bevy::{input::mouse::AccumulatedMouseMotion, prelude::*, window::{CursorGrabMode, PrimaryWindow}};
@@ -7,7 +7,10 @@ use bevy_rapier3d::{na::wrap, prelude::*};
 use autodefault::autodefault;
 
 // import data from this crate
-use crate::components::SphereOfTear;
+use crate::camera::CameraEffects;
+use crate::components::{Ladder, SphereOfTear, SurfaceMaterial};
+use crate::terrain::structures::{Terrain, TerrainCollider};
+use crate::settings::structures::{GameSettings, MOUSE_SENSITIVITY_SCALE};
 use super::structures::*;
 
 /// Create and setup player
@@ -17,6 +20,7 @@ pub fn setup(
     // mut meshes: ResMut<Assets<Mesh>>,
     // mut materials: ResMut<Assets<StandardMaterial>>,
     have_player: Query<Option<&PlayerComponent>>,
+    settings: Res<GameSettings>,
 ) {
     // if the player already exists, then exit
     if let Ok(_) = have_player.get_single() {
@@ -31,20 +35,32 @@ pub fn setup(
         InheritedVisibility::HIDDEN,
         PlayerComponent::default(),
 
-        // Mesh3d(meshes.add(Cylinder::new(PLAYER_RADIUS, PLAYER_HEIGHT))),
+        // Mesh3d(meshes.add(Capsule3d::new(PLAYER_RADIUS, PLAYER_HEIGHT * 2.0 - PLAYER_RADIUS * 2.0))),
         // MeshMaterial3d(materials.add(StandardMaterial::default())),
 
         RigidBody::KinematicVelocityBased, // physics data
-        Collider::cylinder(PLAYER_HEIGHT, PLAYER_RADIUS),
+        player_capsule(PLAYER_HEIGHT),
     )).with_children(|parent| {
             parent.spawn(( // add player's camera
-                Projection::from(PerspectiveProjection { fov: 90.0_f32.to_radians() }),
+                Projection::from(PerspectiveProjection { fov: settings.fov.to_radians() }),
                 Transform::from_translation(CAMERA_WALK_TRANSLATION),
                 PlayerCameraPivot {},
+                CameraEffects::default(),
                 Camera3d::default(),
+                SpatialListener::new(0.2),
 
                 // Mesh3d(meshes.add(Cuboid::default())),
                 // MeshMaterial3d(materials.add(StandardMaterial::default())),
+            )).with_child(( // add player's flashlight
+                #[cfg(debug_assertions)] Name::new("Flashlight"),
+                SpotLight {
+                    intensity: 0.0,
+                    range: 25.0,
+                    inner_angle: 0.25,
+                    outer_angle: 0.45,
+                    shadows_enabled: true,
+                },
+                Flashlight::default(),
             ));
         });
 }
@@ -57,6 +73,7 @@ pub fn update_input(
     mut input: ResMut<PlayersInput>
 ) {
     if !input_enabled.0 { // if input disabled, exit
+        input.jump_pressed = false;
         return;
     } else if let Err(_) = player_query.get_single() {
         return;
@@ -73,6 +90,9 @@ pub fn update_input(
 
     // get action's input
     input.crouch = keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    // lean is Q/E by design, but E is interaction, so lean right is C
+    input.lean_left = keys.pressed(KeyCode::KeyQ);
+    input.lean_right = keys.pressed(KeyCode::KeyC);
 
     // if stamina > 5.0, player can't run
     let run = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
@@ -80,27 +100,27 @@ pub fn update_input(
 
     // can't jump while crouch
     input.jump = keys.pressed(KeyCode::Space) && !input.crouch;
+    input.jump_pressed = keys.just_pressed(KeyCode::Space) && !input.crouch;
 }
 
-/// set visible cursor
+/// grab cursor by right click, it's released by settings menu (escape).
+/// Cursor isn't grabbed while someone else owns player's input
 pub fn update_cursor_visible(
     mut input_enabled: ResMut<PlayerInputEnabled>,
     mut window_query: Query<&mut Window, With<PrimaryWindow>>,
     buttons: Res<ButtonInput<MouseButton>>,
-    keys: Res<ButtonInput<KeyCode>>,
+    owners: Res<InputOwners>,
 ) {
+    if !owners.0.is_empty() {
+        return;
+    }
+
     for mut window in window_query.iter_mut() {
         if buttons.just_pressed(MouseButton::Right) {
             window.cursor_options.grab_mode = CursorGrabMode::Confined;
             window.cursor_options.visible = false;
             input_enabled.0 = true;
         }
-
-        if keys.just_pressed(KeyCode::Escape) {
-            window.cursor_options.grab_mode = CursorGrabMode::None;
-            window.cursor_options.visible = true;
-            input_enabled.0 = false;
-        }
     }
 }
 
@@ -174,6 +194,10 @@ pub fn update_fear(
         Err(_) => return,
     };
 
+    if player.fear_frozen {
+        return;
+    }
+
     // player in sphere decrase fear points
     let mut in_sphere = false;
     for sphere in spheres_query.iter() {
@@ -192,6 +216,36 @@ pub fn update_fear(
     }
 }
 
+/// turn flashlight on and off by F and drain it's battery
+pub fn update_flashlight(
+    mut flashlight_query: Query<(&mut Flashlight, &mut SpotLight)>,
+    input_enabled: Res<PlayerInputEnabled>,
+    keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+) {
+    let Ok((mut flashlight, mut light)) = flashlight_query.get_single_mut() else { return; };
+
+    if input_enabled.0 && keys.just_pressed(KeyCode::KeyF) {
+        flashlight.on = !flashlight.on && flashlight.battery > 0.0;
+    }
+
+    if flashlight.on {
+        flashlight.battery = (flashlight.battery - FLASHLIGHT_DRAIN_RATE * time.delta_secs()).max(0.0);
+
+        // dead battery turns flashlight off
+        if flashlight.battery <= 0.0 {
+            flashlight.on = false;
+        }
+    }
+
+    // light becomes weaker when battery is low
+    light.intensity = if flashlight.on {
+        FLASHLIGHT_INTENSITY * flashlight.battery.sqrt()
+    } else {
+        0.0
+    };
+}
+
 /// update player's rotation
 pub fn update_rotation(
     mut model_query: Query<&mut Transform, (With<PlayerComponent>, Without<PlayerCameraPivot>)>,
@@ -205,13 +259,19 @@ pub fn update_rotation(
 
 /// move player's kinematic character
 pub fn move_character(
-    mut player_query: Query<(Entity, &Collider, &mut Transform, &mut PlayerControllerData)>,
+    mut player_query: Query<(Entity, &mut Collider, &mut Transform, &mut PlayerControllerData)>,
     mut pivot_query: Query<&mut Transform, (With<PlayerCameraPivot>, Without<PlayerControllerData>)>,
     mut rapier_context: Query<(
         &mut RapierContextSimulation, &RapierContextColliders, &RapierQueryPipeline, &mut RapierRigidBodySet
     )>,        
+    ladders_query: Query<(Entity, &Ladder, &GlobalTransform)>,
+    bodies_query: Query<(&RigidBody, &GlobalTransform)>,
+    parents: Query<&Parent>,
     mouse_accumulated_motion: Res<AccumulatedMouseMotion>,
+    mut landed: EventWriter<PlayerLanded>,
     input_enabled: Res<PlayerInputEnabled>,
+    movement: Res<MovementSettings>,
+    settings: Res<GameSettings>,
     input: Res<PlayersInput>,
     time: Res<Time>,
 ) {
@@ -228,65 +288,53 @@ pub fn move_character(
 
     // get player's data
     let (
-        player_entity, player_collider,
+        player_entity, mut player_collider,
         mut player_transform, mut player_controller
     ) = player_query.single_mut();
 
-    let crouch: bool;
-    if player_controller.crouched || input.crouch {
-        // If there is something above the player and player crouched, then
-        // player can't stop crouch
-        let mut casts = false;
-        let ray_cast_context = (rapier_context.1, rapier_context.2, &rapier_context.3);
-
-        /*
-            This cycle check a points and if there is somethins above the player,
-            then player can't stop crouch. This is a picture of ray cast, where
-            "#" is a player contour, "*" is a ray cast and "@" is a center
-                     ####*#####   
-                   ##         ##
-                  ##           ##
-                 ##             ##
-                *##      *       #*
-                 ##             ##
-                  ##           ##
-                   ##         ##
-                    #####*#####
-        */
-
-        for i in 0..5 {
-            let mut x_coord: f32 = 0.0;
-            let mut z_coord: f32 = 0.0;
-
-            if i == 1 {
-                x_coord = PLAYER_RADIUS;
-            } else if i == 2 {
-                x_coord = -PLAYER_RADIUS;
-            } else if i == 3 {
-                z_coord = PLAYER_RADIUS;
-            } else if i == 4 {
-                z_coord = -PLAYER_RADIUS;
-            }
+    // player doesn't fall while level's colliders are creating
+    if player_controller.frozen {
+        return;
+    }
 
-            if let Some(_) = crouch_ray_cast(
-                ray_cast_context, player_entity,
-                &player_transform.translation, x_coord, z_coord
-            ) {
-                casts = true;
-                break;
-            }
-        }
+    let dt = time.delta_secs();
+
+    // while crouch capsule shrinks, and it's top can't rise into ceiling
+    let crouch_input = input.crouch && !player_controller.noclip;
+    let mut target_height = if crouch_input { PLAYER_CROUCH_HEIGHT } else { PLAYER_HEIGHT };
+    if target_height > player_controller.height {
+        let (offset, probe) = headroom_probe(player_controller.height);
+        let headroom = rapier_context.2.cast_shape(
+            rapier_context.1, &*rapier_context.3,
+            player_transform.translation + offset, Quat::IDENTITY, Vec3::Y, &probe,
+            ShapeCastOptions::with_max_time_of_impact((target_height - player_controller.height) * 2.0),
+            QueryFilter::new().exclude_collider(player_entity).exclude_sensors(),
+        );
+
+        let ceiling = headroom.map(|(_, hit)| hit.time_of_impact);
+        target_height = headroom_height(player_controller.height, target_height, ceiling);
+    }
 
-        // set player crouch at the moment
-        crouch = input.crouch || casts;
-    } else {
-        crouch = false;
+    // smoothly change height, feet stay on place
+    let height = move_towards(player_controller.height, target_height, CROUCH_TRANSITION_SPEED * dt);
+    if height != player_controller.height {
+        player_transform.translation.y += height - player_controller.height;
+        player_controller.height = height;
+        *player_collider = player_capsule(height);
     }
 
+    // player is crouched until he fully stands up
+    let crouch = height < PLAYER_HEIGHT - 0.01;
+    player_controller.crouched = crouch;
+    let player_collider = &*player_collider;
+
     // get mouse delta
-    let mouse_delta;
+    let mut mouse_delta;
     if input_enabled.0 {
-        mouse_delta = mouse_accumulated_motion.delta * 0.0032;
+        mouse_delta = mouse_accumulated_motion.delta * settings.mouse_sensitivity * MOUSE_SENSITIVITY_SCALE;
+        if settings.invert_y {
+            mouse_delta.y = -mouse_delta.y;
+        }
     } else {
         mouse_delta = Vec2::ZERO;
     }
@@ -306,14 +354,21 @@ pub fn move_character(
     // get player's speed
     let mut normalized_move = Vec3::ZERO;
 
+    // sprint speeds up gradually
+    let (sprint_target, sprint_time) = if input.run && !crouch {
+        (1.0, movement.sprint_ramp_up)
+    } else {
+        (0.0, movement.sprint_ramp_down)
+    };
+    let sprint_step = if sprint_time > 0.0 { dt / sprint_time } else { 1.0 };
+    player_controller.sprint = move_towards(player_controller.sprint, sprint_target, sprint_step);
+
     // get player speed
     let player_speed: f32;
     if crouch {
         player_speed = CROUCH_SPEED;
-    } else if input.run {
-        player_speed = RUN_SPEED;
     } else {
-        player_speed = WALK_SPEED;
+        player_speed = WALK_SPEED.lerp(RUN_SPEED, player_controller.sprint);
     }
 
     // assing initial input directional values
@@ -334,42 +389,261 @@ pub fn move_character(
     // normalize (x and z axis movement)
     normalized_move = normalized_move.normalize_or_zero() * player_speed;
 
+    // in noclip mode player flies through geometry
+    if player_controller.noclip {
+        if input.jump { normalized_move.y += player_speed }
+        if input.crouch { normalized_move.y -= player_speed }
+
+        player_controller.velocity = Vec3::ZERO;
+        player_controller.grounded = false;
+        player_controller.ladder = None;
+        player_controller.transition = None;
+        player_transform.translation += normalized_move * NOCLIP_SPEED_SCALE * time.delta_secs();
+        return;
+    }
+
+    let position = player_transform.translation;
+    let look_direction = Vec3::new(-z_fac, 0.0, -x_fac);
+
+    // player is moved by animation (enter and exit of ladder, mantling)
+    if let Some(mut transition) = player_controller.transition {
+        transition.progress += dt / transition.duration;
+        player_transform.translation = transition.position();
+
+        player_controller.velocity = Vec3::ZERO;
+        player_controller.grounded = false;
+        player_controller.transition = (transition.progress < 1.0).then_some(transition);
+        return;
+    }
+
+    // climb on ladder
+    if let Some(ladder_entity) = player_controller.ladder {
+        if let Ok((_, ladder, ladder_transform)) = ladders_query.get(ladder_entity) {
+            let local = ladder.local_point(ladder_transform, position);
+            let ladder_forward = ladder_transform.forward().with_y(0.0).normalize_or_zero();
+            let climb = (input.forward as i32 - input.backward as i32) as f32 * ladder.climb_speed * dt;
+
+            // jump off ladder backward, as far as nothing blocks player
+            if input.jump_pressed {
+                let target = free_position(
+                    (rapier_context.1, rapier_context.2, &*rapier_context.3),
+                    player_entity, height, position, position - ladder_forward * LADDER_JUMP_OFF_DISTANCE,
+                );
+
+                player_controller.ladder = None;
+                player_controller.transition = Some(ControllerTransition::new(
+                    position, target, LADDER_JUMP_OFF_TIME, 0.0
+                ));
+                return;
+            }
+
+            // get off ladder at top onto floor
+            if climb > 0.0 && local.y + climb > ladder.half_extents.y {
+                let ledge = find_ledge(
+                    (rapier_context.1, rapier_context.2, &*rapier_context.3),
+                    player_entity, height, position, ladder_forward,
+                    LADDER_EXIT_REACH, LADDER_EXIT_MIN_HEIGHT..LADDER_EXIT_MAX_HEIGHT,
+                );
+
+                if let Some(target) = ledge {
+                    player_controller.ladder = None;
+                    player_controller.transition = Some(ControllerTransition::new(
+                        position, target, LADDER_EXIT_TIME, 0.6
+                    ));
+                }
+                return;
+            }
+
+            let move_output = rapier_context.0.move_shape(
+                rapier_context.1, rapier_context.2, &mut *rapier_context.3,
+                Vec3::Y * climb,
+                player_collider,
+                position,
+                Quat::IDENTITY,
+                player_controller.mass,
+                &MoveShapeOptions { autostep: None, ..default() },
+                QueryFilter::new().exclude_collider(player_entity).exclude_sensors(), |_| {}
+            );
+
+            player_transform.translation += move_output.effective_translation;
+            player_controller.velocity = Vec3::ZERO;
+            player_controller.grounded = false;
+
+            // steps on ladder sound like it's surface
+            player_controller.ground = Some(ladder_entity);
+            player_controller.travelled += move_output.effective_translation.y.abs();
+
+            // get off ladder at bottom
+            if climb < 0.0 && (move_output.grounded || local.y + climb < -ladder.half_extents.y) {
+                player_controller.ladder = None;
+            }
+            return;
+        }
+
+        // ladder was despawned
+        player_controller.ladder = None;
+    }
+
+    // grab ladder when player walks to it
+    if input.forward {
+        let ladder = ladders_query.iter().find(|(_, ladder, ladder_transform)| {
+            ladder.contains(ladder_transform, position)
+                && ladder_transform.forward().dot(look_direction) >= LADDER_GRAB_FACING
+        });
+
+        if let Some((ladder_entity, ladder, ladder_transform)) = ladder {
+            // stand in the middle of ladder or as close to it as walls allow
+            let local = ladder.local_point(ladder_transform, position);
+            let target = free_position(
+                (rapier_context.1, rapier_context.2, &*rapier_context.3),
+                player_entity, height, position, ladder_transform.transform_point(Vec3::Y * local.y),
+            );
+
+            player_controller.ladder = Some(ladder_entity);
+            player_controller.velocity = Vec3::ZERO;
+            player_controller.transition = Some(ControllerTransition::new(
+                position, target, LADDER_ENTER_TIME, 0.0
+            ));
+            return;
+        }
+    }
+
+    // mantle over obstacle in front of player instead of jump
+    if input.jump && input.forward {
+        let ledge = find_mantle_ledge(
+            (rapier_context.1, rapier_context.2, &*rapier_context.3),
+            player_entity, height, position, look_direction,
+        );
+
+        if let Some(target) = ledge {
+            player_controller.velocity = Vec3::ZERO;
+            player_controller.transition = Some(ControllerTransition::new(
+                position, target, MANTLE_TIME, 0.5
+            ));
+            return;
+        }
+    }
+
+    // moving platform carries player
+    let mut platform_move = Vec3::ZERO;
+    if let Some(anchor) = player_controller.platform {
+        if let Ok((_, platform_transform)) = bodies_query.get(anchor.entity) {
+            let feet = position - Vec3::Y * height;
+            platform_move = platform_transform.transform_point(anchor.local) - feet;
+        }
+    }
+    player_controller.platform_velocity = if dt > 0.0 { platform_move / dt } else { Vec3::ZERO };
+
     // update Y - velocity (jump/fly/gravity)
     if player_controller.grounded {
         if player_controller.acceleration.y < 0.0 || player_controller.velocity.y < 0.0 {
             player_controller.acceleration.y = 0.0;
             player_controller.velocity.y = 0.0;
         }
-
-        if input.jump {
-            player_controller.velocity.y += player_controller.jump_force;
-        }
     } else {
         // update gravity
-        if player_controller.velocity.y >= 0.0 {
+        if player_controller.velocity.y >= 0.0 || player_controller.sliding {
             player_controller.acceleration.y = -player_controller.gravity;
         }
     }
 
+    // jump is remembered shortly before landing
+    if input.jump_pressed {
+        player_controller.jump_buffer = movement.jump_buffer_time;
+    } else {
+        player_controller.jump_buffer = (player_controller.jump_buffer - dt).max(0.0);
+    }
+
+    // player can jump shortly after leaving ground
+    if player_controller.grounded {
+        player_controller.coyote = movement.coyote_time;
+    } else {
+        player_controller.coyote = (player_controller.coyote - dt).max(0.0);
+    }
+
+    // player keeps velocity of platform after jump
+    if player_controller.jump_buffer > 0.0 && player_controller.coyote > 0.0 {
+        player_controller.velocity.y = player_controller.jump_force;
+        player_controller.velocity += player_controller.platform_velocity;
+        player_controller.jump_buffer = 0.0;
+        player_controller.coyote = 0.0;
+    }
+
     // add on acceleration * dt
-    let dt = time.delta().as_secs_f32();
     let velocity_change = player_controller.acceleration * dt;
     player_controller.velocity += velocity_change;
 
+    if player_controller.sliding {
+        // on steep slope gravity pulls player down along the slope
+        let normal = player_controller.ground_normal;
+        let into_slope = player_controller.velocity.dot(normal);
+        if into_slope < 0.0 {
+            player_controller.velocity -= normal * into_slope;
+        }
+    } else {
+        // horizontal velocity follows input, surface changes how fast.
+        // In air player keeps momentum and has little control
+        let (acceleration, deceleration, speed_multiplier) = if player_controller.grounded {
+            let traction = player_controller.surface.traction();
+            (
+                movement.ground_acceleration * traction,
+                movement.ground_deceleration * traction,
+                player_controller.surface.speed_multiplier(),
+            )
+        } else {
+            (movement.ground_acceleration * movement.air_control, movement.air_deceleration, 1.0)
+        };
+
+        let target = normalized_move * speed_multiplier;
+        let horizontal = accelerate(
+            player_controller.velocity.with_y(0.0), target,
+            acceleration, deceleration, movement.acceleration_curve, dt,
+        );
+
+        player_controller.velocity.x = horizontal.x;
+        player_controller.velocity.z = horizontal.z;
+    }
+
+    // dynamic bodies push player away, their velocity is read from
+    // rapier, because dynamic bodies haven't `Velocity` component by default
+    let push_shape = Collider::capsule_y(height - PLAYER_RADIUS, PLAYER_RADIUS + DYNAMIC_PUSH_MARGIN);
+    let rigid_bodies = &*rapier_context.3;
+    let mut pushes = Vec::new();
+    rapier_context.2.intersections_with_shape(
+        rapier_context.1, rigid_bodies,
+        position, Quat::IDENTITY, &push_shape,
+        QueryFilter::new().exclude_collider(player_entity).exclude_sensors(),
+        |entity| {
+            let Some(body) = find_body(entity, &bodies_query, &parents) else { return true; };
+            let Ok((RigidBody::Dynamic, body_transform)) = bodies_query.get(body) else { return true; };
+            let Some(linvel) = body_linvel(rigid_bodies, body) else { return true; };
+
+            let away = (position - body_transform.translation()).with_y(0.0).normalize_or_zero();
+            pushes.push((away, linvel.dot(away)));
+            true
+        },
+    );
+
+    for (away, speed) in pushes {
+        let current = player_controller.velocity.dot(away);
+        if speed > current {
+            player_controller.velocity += away * (speed - current);
+        }
+    }
+
     // clamp y-speed to terminal velocity values
     player_controller.velocity.y = player_controller.velocity.y.clamp(
         -player_controller.terminal_velocity, player_controller.terminal_velocity
     );
 
-    // add velocity to move and apply delta time to queued move
-    normalized_move += player_controller.velocity;
-    normalized_move *= dt;
+    // apply delta time to velocity and add platform's move
+    let translation = player_controller.velocity * dt + platform_move;
 
     // we only want auto-stepping if the player is grounded
     let step: Option<CharacterAutostep>;
     if player_controller.grounded {
         step = Some(CharacterAutostep {
-            max_height: CharacterLength::Absolute(1.65),
+            max_height: CharacterLength::Absolute(AUTOSTEP_HEIGHT),
             min_width: CharacterLength::Absolute(0.5),
             include_dynamic_bodies: true,
         });
@@ -377,85 +651,254 @@ pub fn move_character(
         step = None;
     };
 
-    // update rotation for crouch
-    let rotation: Quat;
-    if crouch {
-        let needs_rotation =
-            Quat::from_rotation_x(player_controller.rotation.y) * Quat::from_rotation_x(90_f32.to_radians());
-        rotation = Quat::from_rotation_z(90.0_f32.to_radians()) * needs_rotation;
-    } else {
-        rotation = Quat::IDENTITY;
-    }
-
     // start move player shape
     let move_output = rapier_context.0.move_shape(
         rapier_context.1, rapier_context.2, &mut *rapier_context.3,
-        normalized_move,
+        translation,
         player_collider,
         player_transform.translation,
-        rotation,
+        Quat::IDENTITY,
         player_controller.mass,
         &MoveShapeOptions {
             autostep: step,
             slide: true,
             min_slope_slide_angle: 30.0_f32.to_radians(),
-            max_slope_climb_angle: 45.0_f32.to_radians(),
+            max_slope_climb_angle: STEEP_SLOPE_ANGLE,
             ..default()
         },
-        QueryFilter::new().exclude_collider(player_entity), |_| {}
+        QueryFilter::new().exclude_collider(player_entity).exclude_sensors(), |_| {}
     );
 
     // update grounded
-    if let Some(_) = rapier_context.2.intersection_with_shape(
+    let ground = rapier_context.2.intersection_with_shape(
         rapier_context.1, &mut *rapier_context.3,
-        player_transform.translation + Vec3::new(0.0, -PLAYER_HEIGHT, 0.0),
+        player_transform.translation + Vec3::new(0.0, -height, 0.0),
         Quat::IDENTITY,
         &Collider::cylinder(0.35, PLAYER_RADIUS - 0.1),
-        QueryFilter::new().exclude_collider(player_entity)
-    ) {
-        player_controller.grounded = player_controller.velocity.y <= 0.0;
-    } else {
-        player_controller.grounded = false;
-    }
+        QueryFilter::new().exclude_collider(player_entity).exclude_sensors()
+    );
 
     // update player position
     player_transform.translation += move_output.effective_translation;
 
-    // If player crouched in last frame and end crouch in this frame player need
-    // to up, because if you don't do this player will get stuck
-    if player_controller.crouched && !crouch {
-        player_transform.translation += Vec3::new(0.0, PLAYER_HEIGHT - PLAYER_RADIUS, 0.0);
+    // slope under the player
+    let ground_normal = ground.and_then(|_| {
+        rapier_context.2.cast_ray_and_get_normal(
+            rapier_context.1, &*rapier_context.3,
+            player_transform.translation, Vec3::NEG_Y, height + PLAYER_RADIUS * 2.0, true,
+            QueryFilter::new().exclude_collider(player_entity).exclude_sensors()
+        )
+    }).map(|(_, intersection)| intersection.normal).unwrap_or(Vec3::Y);
+
+    let steep = ground_normal.angle_between(Vec3::Y) > STEEP_SLOPE_ANGLE;
+    player_controller.ground_normal = ground_normal;
+    player_controller.sliding = ground.is_some() && steep;
+
+    let was_grounded = player_controller.grounded;
+    player_controller.grounded = ground.is_some() && !steep && player_controller.velocity.y <= 0.0;
+    player_controller.ground = ground;
+
+    // camera reacts to landing
+    if !was_grounded && player_controller.grounded {
+        landed.send(PlayerLanded { fall_speed: -player_controller.velocity.y });
     }
 
-    // update crouch state
-    player_controller.crouched = input.crouch;
- 
-    // update cam translation
-    if crouch {
-        pivot_transform.translation = CAMERA_CROUCH_TRANSLATION;
+    // remember point on moving platform
+    let platform = ground.filter(|_| player_controller.grounded)
+        .and_then(|ground| find_body(ground, &bodies_query, &parents))
+        .and_then(|body| bodies_query.get(body).ok().map(|(rigid_body, transform)| (body, rigid_body, transform)))
+        .filter(|(_, rigid_body, _)| matches!(
+            rigid_body, RigidBody::KinematicPositionBased | RigidBody::KinematicVelocityBased
+        ));
+
+    player_controller.platform = platform.map(|(entity, _, platform_transform)| {
+        let feet = player_transform.translation - Vec3::Y * height;
+        PlatformAnchor { entity, local: platform_transform.affine().inverse().transform_point3(feet) }
+    });
+
+    // count distance for footsteps
+    if player_controller.grounded {
+        player_controller.travelled += (move_output.effective_translation - platform_move).xz().length();
+    }
+}
+
+/// find surface under the player
+pub fn update_ground_surface(
+    mut player_query: Query<(&Transform, &mut PlayerControllerData)>,
+    terrain_colliders: Query<(), With<TerrainCollider>>,
+    surfaces: Query<&SurfaceMaterial>,
+    parents: Query<&Parent>,
+    terrain: Res<Terrain>,
+) {
+    let Ok((player_transform, mut player_controller)) = player_query.get_single_mut() else { return; };
+    let Some(ground) = player_controller.ground else { return; };
+
+    let surface = if terrain_colliders.contains(ground) {
+        terrain.surface_at(player_transform.translation.xz())
     } else {
-        pivot_transform.translation = CAMERA_WALK_TRANSLATION;
-    }   player_controller.crouched = crouch;
+        SurfaceMaterial::find(ground, &surfaces, &parents)
+    };
+
+    if player_controller.surface != surface {
+        player_controller.surface = surface;
+    }
 }
 
 // helper functions
 
-/// Ray cast for detect something above the player.
-/// While he crouch.
-#[inline] fn crouch_ray_cast(
-     rapier_context: (&RapierContextColliders, &RapierQueryPipeline, &Mut<RapierRigidBodySet>),
-     player_entity: Entity,
-     translation: &Vec3,
-     x_coord: f32,
-     z_coord: f32
-) -> Option<(Entity, f32)> {
-    rapier_context.1.cast_ray(
-        rapier_context.0, &*rapier_context.2,
-        translation.clone(),
-        Vec3::new(x_coord, 1.0, z_coord),
-        PLAYER_HEIGHT * 1.2,
-        true,
-        QueryFilter::new().exclude_collider(player_entity)
+/// Return capsule of player with half of height
+#[inline] fn player_capsule(height: f32) -> Collider {
+    Collider::capsule_y(height - PLAYER_RADIUS, PLAYER_RADIUS)
+}
+
+/// Move value towards target, but not more than by max delta
+#[inline] fn move_towards(value: f32, target: f32, max_delta: f32) -> f32 {
+    value + (target - value).clamp(-max_delta, max_delta)
+}
+
+/// Change horizontal velocity towards target. Acceleration falls
+/// near target speed, player decelerates when he stops or turns
+#[inline] fn accelerate(
+    velocity: Vec3,
+    target: Vec3,
+    acceleration: f32,
+    deceleration: f32,
+    curve: f32,
+    dt: f32,
+) -> Vec3 {
+    let speed = velocity.length();
+    let target_speed = target.length();
+
+    let rate = if target_speed > 0.0 && speed < target_speed && velocity.dot(target) >= 0.0 {
+        acceleration * (1.0 - speed / target_speed).powf(curve).max(MIN_ACCELERATION_SCALE)
+    } else {
+        deceleration
+    };
+
+    velocity + (target - velocity).clamp_length_max(rate * dt)
+}
+
+/// Find rigid body of collider: check entity and it's parents
+#[inline] fn find_body(
+    entity: Entity,
+    bodies_query: &Query<(&RigidBody, &GlobalTransform)>,
+    parents: &Query<&Parent>,
+) -> Option<Entity> {
+    let mut current = entity;
+
+    // scenes from gltf store rigid body on node, and colliders on mesh
+    for _ in 0..3 {
+        if bodies_query.contains(current) {
+            return Some(current);
+        }
+
+        current = parents.get(current).ok()?.get();
+    }
+
+    None
+}
+
+/// Return linear velocity of rigid body from rapier
+#[inline] fn body_linvel(rigid_bodies: &RapierRigidBodySet, entity: Entity) -> Option<Vec3> {
+    let handle = rigid_bodies.entity2body().get(&entity)?;
+    let linvel = rigid_bodies.bodies.get(*handle)?.linvel();
+    Some(Vec3::new(linvel.x, linvel.y, linvel.z))
+}
+
+/// Find position of player standing on ledge in front of him.
+/// Ledge is searched at `reach` distance in direction, it's
+/// top must be in `heights` above player's feet
+#[inline] fn find_ledge(
+    rapier_context: (&RapierContextColliders, &RapierQueryPipeline, &RapierRigidBodySet),
+    player_entity: Entity,
+    height: f32,
+    translation: Vec3,
+    direction: Vec3,
+    reach: f32,
+    heights: Range<f32>,
+) -> Option<Vec3> {
+    let filter = QueryFilter::new().exclude_collider(player_entity).exclude_sensors();
+    let probe = Collider::cylinder(MANTLE_PROBE_HALF_HEIGHT, PLAYER_RADIUS * 0.9);
+
+    // cast probe down on top of ledge
+    let feet = translation - Vec3::Y * height;
+    let start = feet + direction * reach + Vec3::Y * (heights.end + MANTLE_PROBE_HALF_HEIGHT);
+
+    let (_, hit) = rapier_context.1.cast_shape(
+        rapier_context.0, rapier_context.2,
+        start, Quat::IDENTITY, Vec3::NEG_Y, &probe,
+        ShapeCastOptions::with_max_time_of_impact(heights.end - heights.start),
+        filter,
+    )?;
+
+    // probe started inside of wall, ledge is too high
+    if hit.time_of_impact <= 0.0 {
+        return None;
+    }
+
+    let top = start.y - hit.time_of_impact - MANTLE_PROBE_HALF_HEIGHT;
+    let target = start.with_y(top + height + LEDGE_CLEARANCE);
+
+    // player must fit on ledge
+    rapier_context.1.intersection_with_shape(
+        rapier_context.0, rapier_context.2,
+        target, Quat::IDENTITY, &player_capsule(height), filter
+    ).is_none().then_some(target)
+}
+
+/// Return farthest position on straight way from translation to target,
+/// which player reaches without collisions
+#[inline] fn free_position(
+    rapier_context: (&RapierContextColliders, &RapierQueryPipeline, &RapierRigidBodySet),
+    player_entity: Entity,
+    height: f32,
+    translation: Vec3,
+    target: Vec3,
+) -> Vec3 {
+    let offset = target - translation;
+    let hit = rapier_context.1.cast_shape(
+        rapier_context.0, rapier_context.2,
+        translation, Quat::IDENTITY, offset, &player_capsule(height),
+        // touching colliders (floor under player) don't block move away from them
+        ShapeCastOptions {
+            target_distance: LEDGE_CLEARANCE,
+            stop_at_penetration: false,
+            ..ShapeCastOptions::with_max_time_of_impact(1.0)
+        },
+        QueryFilter::new().exclude_collider(player_entity).exclude_sensors(),
+    );
+
+    match hit {
+        Some((_, hit)) => translation + offset * hit.time_of_impact,
+        None => target,
+    }
+}
+
+/// Find position of player on waist-high obstacle in front of him
+#[inline] fn find_mantle_ledge(
+    rapier_context: (&RapierContextColliders, &RapierQueryPipeline, &RapierRigidBodySet),
+    player_entity: Entity,
+    height: f32,
+    translation: Vec3,
+    direction: Vec3,
+) -> Option<Vec3> {
+    let filter = QueryFilter::new().exclude_collider(player_entity).exclude_sensors();
+    let probe = Collider::cylinder(MANTLE_PROBE_HALF_HEIGHT, PLAYER_RADIUS * 0.9);
+
+    // cast probe forward at height of obstacle
+    let feet = translation - Vec3::Y * height;
+    let (_, hit) = rapier_context.1.cast_shape(
+        rapier_context.0, rapier_context.2,
+        feet + Vec3::Y * (MANTLE_MIN_HEIGHT + MANTLE_PROBE_HALF_HEIGHT), Quat::IDENTITY,
+        direction, &probe,
+        ShapeCastOptions::with_max_time_of_impact(MANTLE_REACH),
+        filter,
+    )?;
+
+    // player stands on obstacle behind it's edge
+    let reach = hit.time_of_impact + PLAYER_RADIUS * 1.9;
+    find_ledge(
+        rapier_context, player_entity, height,
+        translation, direction, reach, MANTLE_MIN_HEIGHT..MANTLE_MAX_HEIGHT,
     )
 }
-   
@@ -0,0 +1,73 @@
+//! Loader of data assets from ron files. Every data asset
+//! (sequences, objectives, etc.) is registered with own extension.
+use std::{fmt, marker::PhantomData};
+
+use bevy::asset::{io::Reader, Asset, AssetLoader, LoadContext};
+use serde::de::DeserializeOwned;
+
+/// Error of ron asset loading
+#[derive(Debug)]
+pub enum RonLoaderError {
+    /// file can't be read
+    Io(std::io::Error),
+    /// file isn't valid asset
+    Ron(ron::error::SpannedError),
+}
+
+impl fmt::Display for RonLoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RonLoaderError::Io(error) => write!(f, "can't read asset: {error}"),
+            RonLoaderError::Ron(error) => write!(f, "can't parse asset: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for RonLoaderError {}
+
+impl From<std::io::Error> for RonLoaderError {
+    fn from(error: std::io::Error) -> Self {
+        RonLoaderError::Io(error)
+    }
+}
+
+impl From<ron::error::SpannedError> for RonLoaderError {
+    fn from(error: ron::error::SpannedError) -> Self {
+        RonLoaderError::Ron(error)
+    }
+}
+
+/// Loader of asset `T` from ron files with extensions
+pub struct RonLoader<T> {
+    extensions: &'static [&'static str],
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> RonLoader<T> {
+    /// Create loader for files with extensions (for example "sequence.ron")
+    pub fn new(extensions: &'static [&'static str]) -> Self {
+        Self { extensions, _marker: PhantomData }
+    }
+}
+
+impl<T: Asset + DeserializeOwned> AssetLoader for RonLoader<T> {
+    type Asset = T;
+    type Settings = ();
+    type Error = RonLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        Ok(ron::de::from_bytes::<T>(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        self.extensions
+    }
+}
@@ -0,0 +1,30 @@
+//! Save - in this module located saving and loading of game.
+//! Every module syncs own state with `SaveData` in `SaveSet::Sync`,
+//! and this module reads and writes it to file.
+
+// import crates
+use bevy::prelude::*;
+
+// save systems
+pub(super) mod systems;
+pub mod structures;
+
+pub struct SavePlugin;
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(structures::SaveData::default());
+        app.add_event::<structures::SaveGame>();
+        app.add_event::<structures::LoadGame>();
+        app.add_event::<structures::GameLoaded>();
+
+        app.configure_sets(Update, (
+            structures::SaveSet::Read,
+            structures::SaveSet::Sync,
+            structures::SaveSet::Write,
+        ).chain());
+
+        app.add_systems(Update, systems::request_save_or_load.before(structures::SaveSet::Read));
+        app.add_systems(Update, systems::read_save.in_set(structures::SaveSet::Read));
+        app.add_systems(Update, systems::write_save.in_set(structures::SaveSet::Write));
+    }
+}
@@ -0,0 +1,69 @@
+//! This module store save's structures
+use std::{fs, path::Path};
+
+use bevy::{prelude::*, utils::HashMap};
+use serde::{Deserialize, Serialize};
+
+use crate::notes::structures::NoteEntry;
+use crate::objectives::structures::ObjectiveStatus;
+
+// const's
+/// folder of saves
+pub(super) const SAVES_DIR: &str = "saves";
+/// file of save
+pub(super) const SAVE_FILE: &str = "saves/save.ron";
+
+/// Sets of save systems
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SaveSet {
+    /// save file is read to `SaveData`
+    Read,
+    /// modules sync their state with `SaveData`
+    Sync,
+    /// `SaveData` is written to save file
+    Write,
+}
+
+/// All saved data of game
+#[derive(Resource, Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SaveData {
+    /// id of current level
+    pub level: Option<String>,
+    /// statuses of objectives by id
+    pub objectives: HashMap<String, ObjectiveStatus>,
+    /// collected notes
+    pub notes: Vec<NoteEntry>,
+}
+
+impl SaveData {
+    /// Read save from file
+    pub fn read() -> Result<Self, String> {
+        let text = fs::read_to_string(SAVE_FILE).map_err(|error| error.to_string())?;
+        ron::from_str(&text).map_err(|error| error.to_string())
+    }
+
+    /// Write save to file
+    pub fn write(&self) -> Result<(), String> {
+        let text = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|error| error.to_string())?;
+
+        if !Path::new(SAVES_DIR).exists() {
+            fs::create_dir_all(SAVES_DIR).map_err(|error| error.to_string())?;
+        }
+
+        fs::write(SAVE_FILE, text).map_err(|error| error.to_string())
+    }
+}
+
+/// Event: save game
+#[derive(Event, Clone, Copy, Debug)]
+pub struct SaveGame;
+
+/// Event: load game
+#[derive(Event, Clone, Copy, Debug)]
+pub struct LoadGame;
+
+/// Event: save was read, modules must apply `SaveData`
+#[derive(Event, Clone, Copy, Debug)]
+pub struct GameLoaded;
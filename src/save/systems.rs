@@ -0,0 +1,57 @@
+//! implementation of save systems
+
+// import crates
+use bevy::prelude::*;
+
+// import data from this crate
+use super::structures::*;
+
+/// quick save by F5 and quick load by F9
+pub fn request_save_or_load(
+    mut save: EventWriter<SaveGame>,
+    mut load: EventWriter<LoadGame>,
+    keys: Res<ButtonInput<KeyCode>>,
+) {
+    if keys.just_pressed(KeyCode::F5) {
+        save.send(SaveGame);
+    }
+
+    if keys.just_pressed(KeyCode::F9) {
+        load.send(LoadGame);
+    }
+}
+
+/// read save file
+pub fn read_save(
+    mut load: EventReader<LoadGame>,
+    mut loaded: EventWriter<GameLoaded>,
+    mut data: ResMut<SaveData>,
+) {
+    if load.read().count() == 0 {
+        return;
+    }
+
+    match SaveData::read() {
+        Ok(save) => {
+            *data = save;
+            loaded.send(GameLoaded);
+            info!("game loaded");
+        },
+        Err(error) => error!("can't load game: {error}"),
+    }
+}
+
+/// write save file
+pub fn write_save(
+    mut save: EventReader<SaveGame>,
+    data: Res<SaveData>,
+) {
+    if save.read().count() == 0 {
+        return;
+    }
+
+    match data.write() {
+        Ok(()) => info!("game saved"),
+        Err(error) => error!("can't save game: {error}"),
+    }
+}
@@ -0,0 +1,29 @@
+//! Settings - in this module located game's settings
+//! (graphics, audio, controls, language), they are stored
+//! in config file and changed from settings menu
+
+// import crates
+use bevy::prelude::*;
+
+use crate::components::HotkeySystems;
+
+// settings systems
+pub(super) mod systems;
+pub mod structures;
+
+pub struct SettingsPlugin;
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(structures::GameSettings::load());
+        app.insert_resource(structures::SettingsMenuState::default());
+
+        app.add_systems(Startup, systems::setup_settings_menu);
+        app.add_systems(Update, (
+            systems::toggle_settings_menu.in_set(HotkeySystems),
+            systems::press_setting_buttons,
+            systems::update_setting_values,
+            systems::apply_settings,
+            systems::apply_shadows_to_new_lights,
+        ).chain());
+    }
+}
@@ -0,0 +1,348 @@
+//! This module store settings structures, enums and consts
+use std::{fs, path::Path};
+
+use bevy::{
+    prelude::*,
+    window::{MonitorSelection, PresentMode, WindowMode, WindowResolution},
+};
+use educe::Educe;
+use serde::{Deserialize, Serialize};
+
+use crate::localization::structures::Localization;
+use crate::player::structures::SavedInput;
+
+// const's
+/// path to config file with settings
+pub(super) const SETTINGS_PATH: &str = "settings.ron";
+
+/// resolutions which can be chosen in menu
+pub(super) const RESOLUTIONS: [(u32, u32); 5] = [
+    (1280, 720), (1366, 768), (1600, 900), (1920, 1080), (2560, 1440)
+];
+
+/// mouse sensitivity 1.0 is equal to this delta multiplier
+pub(crate) const MOUSE_SENSITIVITY_SCALE: f32 = 0.0032;
+
+/// Quality of shadows
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ShadowQuality {
+    Off,
+    Low,
+    #[default]
+    Medium,
+    High,
+}
+
+impl ShadowQuality {
+    /// all qualities in order
+    pub const ALL: [ShadowQuality; 4] = [
+        ShadowQuality::Off, ShadowQuality::Low, ShadowQuality::Medium, ShadowQuality::High
+    ];
+
+    /// Return key of quality's name
+    pub fn name_key(&self) -> &'static str {
+        match self {
+            ShadowQuality::Off => "settings-shadows-off",
+            ShadowQuality::Low => "settings-shadows-low",
+            ShadowQuality::Medium => "settings-shadows-medium",
+            ShadowQuality::High => "settings-shadows-high",
+        }
+    }
+
+    /// Return size of shadow map
+    pub fn map_size(&self) -> usize {
+        match self {
+            ShadowQuality::Off | ShadowQuality::Low => 1024,
+            ShadowQuality::Medium => 2048,
+            ShadowQuality::High => 4096,
+        }
+    }
+}
+
+/// Language of game
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Language {
+    #[default]
+    English,
+    Russian,
+}
+
+impl Language {
+    /// all languages in order
+    pub const ALL: [Language; 2] = [Language::English, Language::Russian];
+
+    /// Return key of language's name
+    pub fn name_key(&self) -> &'static str {
+        match self {
+            Language::English => "language-english",
+            Language::Russian => "language-russian",
+        }
+    }
+}
+
+/// All game's settings, they are saved in config file
+#[derive(Resource, Clone, Debug, Educe, Serialize, Deserialize)]
+#[educe(Default)]
+#[serde(default)]
+pub struct GameSettings {
+    // graphics
+    #[educe(Default = (1280, 720))] /// window's resolution
+    pub resolution: (u32, u32),
+
+    #[educe(Default = false)] /// borderless fullscreen window
+    pub fullscreen: bool,
+
+    #[educe(Default = true)] /// vertical synchronization
+    pub vsync: bool,
+
+    #[educe(Default = 90.0)] /// field of view (in degrees)
+    pub fov: f32,
+
+    #[educe(Default = ShadowQuality::Medium)] /// quality of shadows
+    pub shadows: ShadowQuality,
+
+    // controls
+    #[educe(Default = 1.0)] /// mouse sensitivity multiplier
+    pub mouse_sensitivity: f32,
+
+    #[educe(Default = false)] /// invert vertical mouse axis
+    pub invert_y: bool,
+
+    // audio
+    #[educe(Default = 1.0)] /// master volume
+    pub master_volume: f32,
+
+    #[educe(Default = 1.0)] /// effects volume
+    pub sfx_volume: f32,
+
+    #[educe(Default = 1.0)] /// ambience volume
+    pub ambience_volume: f32,
+
+    #[educe(Default = 1.0)] /// music volume
+    pub music_volume: f32,
+
+    #[educe(Default = 1.0)] /// voices volume
+    pub voice_volume: f32,
+
+    // other
+    #[educe(Default = Language::English)] /// language of game
+    pub language: Language,
+}
+
+impl GameSettings {
+    /// Load settings from config file, if file doesn't exist
+    /// or can't be read return default settings
+    pub fn load() -> Self {
+        let path = Path::new(SETTINGS_PATH);
+        if !path.exists() {
+            return Self::default();
+        }
+
+        match fs::read_to_string(path).map(|text| ron::from_str::<Self>(&text)) {
+            Ok(Ok(settings)) => settings,
+            Ok(Err(error)) => {
+                warn!("can't parse settings: {error}");
+                Self::default()
+            },
+            Err(error) => {
+                warn!("can't read settings: {error}");
+                Self::default()
+            },
+        }
+    }
+
+    /// Return window with resolution, mode and vsync of settings
+    pub fn window(&self) -> Window {
+        Window {
+            resolution: WindowResolution::new(self.resolution.0 as f32, self.resolution.1 as f32),
+            mode: self.window_mode(),
+            present_mode: self.present_mode(),
+            ..default()
+        }
+    }
+
+    /// Return mode of window
+    pub fn window_mode(&self) -> WindowMode {
+        if self.fullscreen {
+            WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+        } else {
+            WindowMode::Windowed
+        }
+    }
+
+    /// Return present mode of window
+    pub fn present_mode(&self) -> PresentMode {
+        if self.vsync { PresentMode::AutoVsync } else { PresentMode::AutoNoVsync }
+    }
+
+    /// Save settings to config file
+    pub fn save(&self) {
+        let text = match ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()) {
+            Ok(text) => text,
+            Err(error) => {
+                error!("can't serialize settings: {error}");
+                return;
+            },
+        };
+
+        if let Err(error) = fs::write(SETTINGS_PATH, text) {
+            error!("can't save settings: {error}");
+        }
+    }
+
+    /// Change option by step (-1 or 1)
+    pub fn step(&mut self, option: SettingOption, step: i32) {
+        let step_f32 = step as f32;
+
+        match option {
+            SettingOption::Resolution => {
+                let current = RESOLUTIONS.iter()
+                    .position(|resolution| *resolution == self.resolution)
+                    .unwrap_or(0);
+                self.resolution = RESOLUTIONS[cycle(current, step, RESOLUTIONS.len())];
+            },
+            SettingOption::Fullscreen => self.fullscreen = !self.fullscreen,
+            SettingOption::Vsync => self.vsync = !self.vsync,
+            SettingOption::Fov => self.fov = (self.fov + step_f32 * 5.0).clamp(60.0, 120.0),
+            SettingOption::Shadows => {
+                let current = ShadowQuality::ALL.iter()
+                    .position(|quality| *quality == self.shadows)
+                    .unwrap_or(0);
+                self.shadows = ShadowQuality::ALL[cycle(current, step, ShadowQuality::ALL.len())];
+            },
+            SettingOption::Sensitivity => {
+                self.mouse_sensitivity = (self.mouse_sensitivity + step_f32 * 0.1).clamp(0.1, 5.0);
+            },
+            SettingOption::InvertY => self.invert_y = !self.invert_y,
+            SettingOption::MasterVolume => step_volume(&mut self.master_volume, step_f32),
+            SettingOption::SfxVolume => step_volume(&mut self.sfx_volume, step_f32),
+            SettingOption::AmbienceVolume => step_volume(&mut self.ambience_volume, step_f32),
+            SettingOption::MusicVolume => step_volume(&mut self.music_volume, step_f32),
+            SettingOption::VoiceVolume => step_volume(&mut self.voice_volume, step_f32),
+            SettingOption::Language => {
+                let current = Language::ALL.iter()
+                    .position(|language| *language == self.language)
+                    .unwrap_or(0);
+                self.language = Language::ALL[cycle(current, step, Language::ALL.len())];
+            },
+        }
+    }
+
+    /// Return text of option's value
+    pub fn value_text(&self, option: SettingOption, localization: &Localization) -> String {
+        match option {
+            SettingOption::Resolution => format!("{}x{}", self.resolution.0, self.resolution.1),
+            SettingOption::Fullscreen => on_off(self.fullscreen, localization),
+            SettingOption::Vsync => on_off(self.vsync, localization),
+            SettingOption::Fov => format!("{:.0}", self.fov),
+            SettingOption::Shadows => localization.get(self.shadows.name_key()).into(),
+            SettingOption::Sensitivity => format!("{:.1}", self.mouse_sensitivity),
+            SettingOption::InvertY => on_off(self.invert_y, localization),
+            SettingOption::MasterVolume => percent(self.master_volume),
+            SettingOption::SfxVolume => percent(self.sfx_volume),
+            SettingOption::AmbienceVolume => percent(self.ambience_volume),
+            SettingOption::MusicVolume => percent(self.music_volume),
+            SettingOption::VoiceVolume => percent(self.voice_volume),
+            SettingOption::Language => localization.get(self.language.name_key()).into(),
+        }
+    }
+}
+
+/// Options of settings menu
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SettingOption {
+    Resolution,
+    Fullscreen,
+    Vsync,
+    Fov,
+    Shadows,
+    Sensitivity,
+    InvertY,
+    MasterVolume,
+    SfxVolume,
+    AmbienceVolume,
+    MusicVolume,
+    VoiceVolume,
+    Language,
+}
+
+impl SettingOption {
+    /// all options in menu's order
+    pub const ALL: [SettingOption; 13] = [
+        SettingOption::Resolution, SettingOption::Fullscreen, SettingOption::Vsync,
+        SettingOption::Fov, SettingOption::Shadows, SettingOption::Sensitivity,
+        SettingOption::InvertY, SettingOption::MasterVolume, SettingOption::SfxVolume,
+        SettingOption::AmbienceVolume, SettingOption::MusicVolume, SettingOption::VoiceVolume,
+        SettingOption::Language,
+    ];
+
+    /// Return key of option's label in menu
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            SettingOption::Resolution => "settings-resolution",
+            SettingOption::Fullscreen => "settings-fullscreen",
+            SettingOption::Vsync => "settings-vsync",
+            SettingOption::Fov => "settings-fov",
+            SettingOption::Shadows => "settings-shadows",
+            SettingOption::Sensitivity => "settings-sensitivity",
+            SettingOption::InvertY => "settings-invert-y",
+            SettingOption::MasterVolume => "settings-master-volume",
+            SettingOption::SfxVolume => "settings-sfx-volume",
+            SettingOption::AmbienceVolume => "settings-ambience-volume",
+            SettingOption::MusicVolume => "settings-music-volume",
+            SettingOption::VoiceVolume => "settings-voice-volume",
+            SettingOption::Language => "settings-language",
+        }
+    }
+}
+
+/// State of settings menu, it owns escape key
+#[derive(Resource, Default)]
+pub struct SettingsMenuState {
+    /// menu is open
+    pub open: bool,
+    /// input and cursor before menu opened
+    pub saved: SavedInput,
+}
+
+/// Root node of settings menu
+#[derive(Component)]
+pub struct SettingsMenu;
+
+/// Button which changes option
+#[derive(Component)]
+pub struct SettingButton {
+    /// option of this button
+    pub option: SettingOption,
+    /// step of change (-1 or 1)
+    pub step: i32,
+}
+
+/// Text with option's value
+#[derive(Component)]
+pub struct SettingValueText(pub SettingOption);
+
+/// Marker of light, whose shadows were disabled by settings
+#[derive(Component)]
+pub struct ShadowsDisabledBySettings;
+
+// helper functions
+
+/// Return index moved by step in cycle of `len` elements
+#[inline] fn cycle(index: usize, step: i32, len: usize) -> usize {
+    (index as i32 + step).rem_euclid(len as i32) as usize
+}
+
+/// Change volume by step of 10%
+#[inline] fn step_volume(volume: &mut f32, step: f32) {
+    *volume = ((*volume + step * 0.1) * 10.0).round() / 10.0;
+    *volume = volume.clamp(0.0, 1.0);
+}
+
+#[inline] fn on_off(value: bool, localization: &Localization) -> String {
+    localization.get(if value { "settings-on" } else { "settings-off" }).into()
+}
+
+#[inline] fn percent(value: f32) -> String {
+    format!("{:.0}%", value * 100.0)
+}
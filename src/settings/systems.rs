@@ -0,0 +1,255 @@
+//! implementation of settings systems
+
+// import crates
+use bevy::{
+    pbr::{DirectionalLightShadowMap, PointLightShadowMap},
+    prelude::*,
+    window::PrimaryWindow,
+};
+
+// import data from this crate
+use crate::audio::structures::AudioMixer;
+use crate::camera::CameraComponent;
+use crate::localization::structures::{Localization, Localized};
+use crate::player::structures::{InputOwner, InputOwners, PlayerCameraPivot, PlayerInputEnabled, SavedInput};
+use super::structures::*;
+
+/// create hidden settings menu
+pub fn setup_settings_menu(
+    mut commands: Commands,
+) {
+    commands.spawn((
+        #[cfg(debug_assertions)] Name::new("Settings menu"),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.75)),
+        Visibility::Hidden,
+        SettingsMenu,
+    )).with_children(|parent| {
+        parent.spawn(Node {
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(6.0),
+            padding: UiRect::all(Val::Px(20.0)),
+            ..default()
+        }).with_children(|panel| {
+            for option in SettingOption::ALL {
+                panel.spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(10.0),
+                    ..default()
+                }).with_children(|row| {
+                    // label of option
+                    row.spawn((
+                        Node { width: Val::Px(260.0), ..default() },
+                        TextFont { font_size: 24.0, ..default() },
+                        Text::new(""),
+                        Localized::new(option.label_key()),
+                    ));
+
+                    spawn_step_button(row, option, -1);
+
+                    // value of option
+                    row.spawn((
+                        Node { width: Val::Px(140.0), justify_content: JustifyContent::Center, ..default() },
+                        TextFont { font_size: 24.0, ..default() },
+                        TextLayout::new_with_justify(JustifyText::Center),
+                        Text::new(""),
+                        SettingValueText(option),
+                    ));
+
+                    spawn_step_button(row, option, 1);
+                });
+            }
+        });
+    });
+}
+
+/// open and close settings menu by escape, while menu is open
+/// cursor is free and player's input is disabled
+pub fn toggle_settings_menu(
+    mut menu_query: Query<&mut Visibility, With<SettingsMenu>>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+    mut input_enabled: ResMut<PlayerInputEnabled>,
+    mut menu: ResMut<SettingsMenuState>,
+    mut input_owners: ResMut<InputOwners>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    settings: Res<GameSettings>,
+) {
+    let was_open = menu.open;
+
+    if keys.just_pressed(KeyCode::Escape) {
+        menu.open = !menu.open;
+    } else if buttons.just_pressed(MouseButton::Right) {
+        // player returns to game
+        menu.open = false;
+    }
+
+    if was_open == menu.open {
+        return;
+    }
+
+    if menu.open {
+        menu.saved = SavedInput::take(&mut input_enabled, window_query.get_single_mut().ok());
+        input_owners.0.insert(InputOwner::Settings);
+    } else {
+        menu.saved.restore(&mut input_enabled, window_query.get_single_mut().ok());
+        input_owners.0.remove(&InputOwner::Settings);
+
+        // save settings when menu is closed
+        settings.save();
+    }
+
+    for mut visibility in menu_query.iter_mut() {
+        *visibility = if menu.open { Visibility::Inherited } else { Visibility::Hidden };
+    }
+}
+
+/// change settings by menu's buttons
+pub fn press_setting_buttons(
+    buttons_query: Query<(&Interaction, &SettingButton), Changed<Interaction>>,
+    mut settings: ResMut<GameSettings>,
+) {
+    for (interaction, button) in buttons_query.iter() {
+        if *interaction == Interaction::Pressed {
+            settings.step(button.option, button.step);
+        }
+    }
+}
+
+/// update texts of values in menu
+pub fn update_setting_values(
+    mut texts_query: Query<(&mut Text, &SettingValueText)>,
+    localization: Res<Localization>,
+    settings: Res<GameSettings>,
+) {
+    if !settings.is_changed() && !localization.is_changed() {
+        return;
+    }
+
+    for (mut text, value) in texts_query.iter_mut() {
+        **text = settings.value_text(value.0, &localization);
+    }
+}
+
+/// apply settings to window, camera, lights and mixer
+pub fn apply_settings(
+    mut commands: Commands,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+    mut projection_query: Query<&mut Projection, Or<(With<PlayerCameraPivot>, With<CameraComponent>)>>,
+    mut point_lights: Query<(Entity, &mut PointLight, Has<ShadowsDisabledBySettings>)>,
+    mut spot_lights: Query<(Entity, &mut SpotLight, Has<ShadowsDisabledBySettings>)>,
+    mut directional_lights: Query<(Entity, &mut DirectionalLight, Has<ShadowsDisabledBySettings>)>,
+    mut directional_shadow_map: ResMut<DirectionalLightShadowMap>,
+    mut point_shadow_map: ResMut<PointLightShadowMap>,
+    mut mixer: ResMut<AudioMixer>,
+    settings: Res<GameSettings>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    // window
+    for mut window in window_query.iter_mut() {
+        window.resolution.set(settings.resolution.0 as f32, settings.resolution.1 as f32);
+        window.mode = settings.window_mode();
+        window.present_mode = settings.present_mode();
+    }
+
+    // camera
+    for mut projection in projection_query.iter_mut() {
+        if let Projection::Perspective(perspective) = &mut *projection {
+            perspective.fov = settings.fov.to_radians();
+        }
+    }
+
+    // shadows
+    directional_shadow_map.size = settings.shadows.map_size();
+    point_shadow_map.size = settings.shadows.map_size();
+
+    let shadows_off = settings.shadows == ShadowQuality::Off;
+    for (entity, mut light, disabled) in point_lights.iter_mut() {
+        switch_shadows(&mut commands, entity, &mut light.shadows_enabled, disabled, shadows_off);
+    }
+    for (entity, mut light, disabled) in spot_lights.iter_mut() {
+        switch_shadows(&mut commands, entity, &mut light.shadows_enabled, disabled, shadows_off);
+    }
+    for (entity, mut light, disabled) in directional_lights.iter_mut() {
+        switch_shadows(&mut commands, entity, &mut light.shadows_enabled, disabled, shadows_off);
+    }
+
+    // audio
+    mixer.master = settings.master_volume;
+    mixer.sfx = settings.sfx_volume;
+    mixer.ambience = settings.ambience_volume;
+    mixer.music = settings.music_volume;
+    mixer.voice = settings.voice_volume;
+}
+
+/// apply shadow setting to lights which are spawned
+/// later (level scenes, forest, sky, light behaviours)
+pub fn apply_shadows_to_new_lights(
+    mut commands: Commands,
+    mut point_lights: Query<(Entity, &mut PointLight, Has<ShadowsDisabledBySettings>), Added<PointLight>>,
+    mut spot_lights: Query<(Entity, &mut SpotLight, Has<ShadowsDisabledBySettings>), Added<SpotLight>>,
+    mut directional_lights: Query<
+        (Entity, &mut DirectionalLight, Has<ShadowsDisabledBySettings>), Added<DirectionalLight>
+    >,
+    settings: Res<GameSettings>,
+) {
+    let shadows_off = settings.shadows == ShadowQuality::Off;
+    for (entity, mut light, disabled) in point_lights.iter_mut() {
+        switch_shadows(&mut commands, entity, &mut light.shadows_enabled, disabled, shadows_off);
+    }
+    for (entity, mut light, disabled) in spot_lights.iter_mut() {
+        switch_shadows(&mut commands, entity, &mut light.shadows_enabled, disabled, shadows_off);
+    }
+    for (entity, mut light, disabled) in directional_lights.iter_mut() {
+        switch_shadows(&mut commands, entity, &mut light.shadows_enabled, disabled, shadows_off);
+    }
+}
+
+// helper functions
+
+/// Spawn button which changes option by step
+fn spawn_step_button(parent: &mut ChildBuilder, option: SettingOption, step: i32) {
+    parent.spawn((
+        Button,
+        Node {
+            width: Val::Px(32.0),
+            height: Val::Px(32.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(Color::srgb(0.15, 0.15, 0.15)),
+        SettingButton { option, step },
+    )).with_child((
+        TextFont { font_size: 24.0, ..default() },
+        Text::new(if step < 0 { "<" } else { ">" }),
+    ));
+}
+
+/// Disable light's shadows (and remember it) or enable them back
+#[inline] fn switch_shadows(
+    commands: &mut Commands,
+    entity: Entity,
+    shadows_enabled: &mut bool,
+    disabled_by_settings: bool,
+    shadows_off: bool,
+) {
+    if shadows_off && *shadows_enabled {
+        *shadows_enabled = false;
+        commands.entity(entity).insert(ShadowsDisabledBySettings);
+    } else if !shadows_off && disabled_by_settings {
+        *shadows_enabled = true;
+        commands.entity(entity).remove::<ShadowsDisabledBySettings>();
+    }
+}
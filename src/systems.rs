@@ -3,10 +3,11 @@
 // import crates
 use bevy::prelude::*;
 use autodefault::autodefault;
-use bevy_rapier3d::prelude::*;
 
 // import this crate
-use crate::components::{CanPickable, InInventory, SphereOfTear};
+use crate::components::{CanPickable, InInventory, Item, SphereOfTear};
+use crate::interaction::{Interactable, ItemPickedUp};
+use crate::notes::structures::Note;
 
 /// Setup system for bevy
 #[autodefault]
@@ -15,7 +16,6 @@ pub fn setup_world(
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
     mut picking_settings: ResMut<MeshPickingSettings>,
-    assets: Res<AssetServer>,
 ) {
     // spawn a 3d camera
     // commands.spawn((
@@ -34,9 +34,20 @@ pub fn setup_world(
         MeshMaterial3d(materials.add(Color::srgb(0.8, 0.1, 0.3))),
         Mesh3d(meshes.add(Cuboid::new(0.7, 0.7, 0.7))),
         Transform::from_xyz(4.0, 4.0, 2.0),
+        Interactable { prompt: "interact-pick-up".into() },
+        Item { name: "item-cube".into() },
         CanPickable
     )).observe(picking);
 
+    // add test note
+    commands.spawn((
+        Name::new("diary note"),
+        MeshMaterial3d(materials.add(Color::srgb(0.86, 0.82, 0.72))),
+        Mesh3d(meshes.add(Cuboid::new(0.3, 0.01, 0.4))),
+        Transform::from_xyz(3.0, 1.0, -2.0),
+        Note { id: "diary".into(), handwritten: true },
+    ));
+
     // create a test sphere
     commands.spawn((
         SphereOfTear(4.0),
@@ -45,14 +56,6 @@ pub fn setup_world(
         MeshMaterial3d(materials.add(Color::BLACK))
     ));
 
-    // main tests scene
-    commands.spawn((
-        SceneRoot(assets.load("gltf/character_controller_demo2.glb#Scene0")),
-        // SceneRoot(assets.load("gltf/spawn.glb#Scene0")),
-        RigidBody::Fixed, Transform::default(),
-        AsyncSceneCollider::default()
-    ));
-
     // spawn a light
     commands.spawn((
        PointLight {
@@ -65,12 +68,18 @@ pub fn setup_world(
 /// interactive systems for picking objects
 fn picking(
     _drag: Trigger<Pointer<Click>>,
-    query: Query<(Entity, &mut Transform), With<CanPickable>>,
+    query: Query<(Entity, Option<&Item>), With<CanPickable>>,
+    mut picked: EventWriter<ItemPickedUp>,
     mut commands: Commands,
 ) {
-    for entity in query.iter() {
-        commands.entity(entity.0).remove::<CanPickable>();
-        commands.entity(entity.0).insert(InInventory);
+    for (entity, item) in query.iter() {
+        commands.entity(entity).remove::<CanPickable>();
+        commands.entity(entity).insert(InInventory);
+
+        picked.send(ItemPickedUp {
+            entity,
+            name: item.map(|item| item.name.clone()).unwrap_or_default(),
+        });
     }
 }
 
@@ -0,0 +1,27 @@
+//! Terrain - ground of forest built from heightmap
+//! (`assets/terrain/`) with LOD, heightfield colliders
+//! and splat-mapped ground materials
+
+// import crates
+use bevy::prelude::*;
+
+// terrain systems
+pub(super) mod systems;
+pub mod structures;
+
+pub struct TerrainPlugin;
+impl Plugin for TerrainPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(MaterialPlugin::<structures::TerrainMaterial>::default());
+
+        app.register_type::<structures::TerrainSettings>();
+        app.insert_resource(structures::TerrainSettings::default());
+
+        app.add_systems(Startup, systems::load_terrain);
+        app.add_systems(Update, (
+            systems::prepare_terrain,
+            systems::build_terrain_chunks,
+            systems::update_terrain_lod,
+        ).chain().after(crate::levels::systems::stream_forest_chunks));
+    }
+}
@@ -0,0 +1,314 @@
+//! This module store terrain's structures and mesh builders
+use bevy::{
+    asset::RenderAssetUsages,
+    pbr::{ExtendedMaterial, MaterialExtension},
+    prelude::*,
+    render::{mesh::{Indices, PrimitiveTopology}, render_resource::{AsBindGroup, ShaderRef}},
+};
+use bevy_rapier3d::prelude::*;
+use educe::Educe;
+
+use crate::components::SurfaceType;
+
+// const's
+/// path to heightmap
+pub(super) const HEIGHTMAP: &str = "terrain/heightmap.png";
+/// path to splat map, every channel is weight of one ground layer
+pub(super) const SPLAT_MAP: &str = "terrain/splatmap.png";
+/// path to terrain shader
+const TERRAIN_SHADER: &str = "shaders/terrain.wgsl";
+/// count of LOD levels, every level has twice less quads
+pub(super) const TERRAIN_LODS: usize = 3;
+
+/// Ground material with splat map
+pub type TerrainMaterial = ExtendedMaterial<StandardMaterial, TerrainSplat>;
+
+/// Extension of standard material, which mixes colors of ground layers
+#[derive(Asset, AsBindGroup, Reflect, Clone, Debug)]
+pub struct TerrainSplat {
+    /// colors of ground layers
+    #[uniform(100)]
+    pub layer_colors: [Vec4; 4],
+    /// weights of layers
+    #[texture(101)]
+    #[sampler(102)]
+    pub splat_map: Handle<Image>,
+}
+
+impl MaterialExtension for TerrainSplat {
+    fn fragment_shader() -> ShaderRef {
+        TERRAIN_SHADER.into()
+    }
+}
+
+/// Settings of terrain
+#[derive(Resource, Clone, Debug, Educe, Reflect)]
+#[reflect(Resource)]
+#[educe(Default)]
+pub struct TerrainSettings {
+    #[educe(Default = 1536.0)] /// size of terrain's side, it's centered in origin
+    pub size: f32,
+
+    #[educe(Default = 20.0)] /// height of white pixel of heightmap
+    pub max_height: f32,
+
+    #[educe(Default = 32)] /// quads on chunk's side at best LOD
+    pub resolution: u32,
+
+    #[educe(Default = [96.0, 192.0])] /// distances, after which LOD is decreased
+    pub lod_distances: [f32; TERRAIN_LODS - 1],
+
+    #[educe(Default = [
+        Color::srgb(0.16, 0.22, 0.1),
+        Color::srgb(0.25, 0.19, 0.13),
+        Color::srgb(0.34, 0.33, 0.31),
+        Color::srgb(0.12, 0.18, 0.12),
+    ])]
+    /// colors of ground layers (channels of splat map)
+    pub layer_colors: [Color; 4],
+
+    #[educe(Default = [SurfaceType::Grass, SurfaceType::Dirt, SurfaceType::Stone, SurfaceType::Grass])]
+    /// surfaces of ground layers, they are used by footsteps
+    pub layer_surfaces: [SurfaceType; 4],
+}
+
+/// Heights and weights of layers, which are read from images
+pub struct TerrainData {
+    /// size of terrain's side
+    pub size: f32,
+    /// heights in meters
+    pub heights: Vec<f32>,
+    pub heights_size: UVec2,
+    /// weights of layers
+    pub splat: Vec<[u8; 4]>,
+    pub splat_size: UVec2,
+    /// surfaces of layers
+    pub surfaces: [SurfaceType; 4],
+}
+
+impl TerrainData {
+    /// Return height in point (x, z) of world
+    pub fn height_at(&self, position: Vec2) -> f32 {
+        let pixel = self.to_pixel(position, self.heights_size);
+        let (x, y) = (pixel.x.floor(), pixel.y.floor());
+        let (fx, fy) = (pixel.x - x, pixel.y - y);
+
+        let sample = |dx: u32, dy: u32| {
+            let px = (x as u32 + dx).min(self.heights_size.x - 1);
+            let py = (y as u32 + dy).min(self.heights_size.y - 1);
+            self.heights[(py * self.heights_size.x + px) as usize]
+        };
+
+        let top = sample(0, 0).lerp(sample(1, 0), fx);
+        let bottom = sample(0, 1).lerp(sample(1, 1), fx);
+        top.lerp(bottom, fy)
+    }
+
+    /// Return normal in point (x, z) of world
+    pub fn normal_at(&self, position: Vec2, step: f32) -> Vec3 {
+        let dx = self.height_at(position - Vec2::X * step) - self.height_at(position + Vec2::X * step);
+        let dz = self.height_at(position - Vec2::Y * step) - self.height_at(position + Vec2::Y * step);
+        Vec3::new(dx, 2.0 * step, dz).normalize()
+    }
+
+    /// Return surface of layer with the biggest weight
+    pub fn surface_at(&self, position: Vec2) -> SurfaceType {
+        let pixel = self.to_pixel(position, self.splat_size).round().as_uvec2().min(self.splat_size - 1);
+        let weights = self.splat[(pixel.y * self.splat_size.x + pixel.x) as usize];
+
+        let layer = (0..4).max_by_key(|layer| weights[*layer]).unwrap_or(0);
+        self.surfaces[layer]
+    }
+
+    /// Build mesh of chunk, vertices are in chunk's space
+    pub fn chunk_mesh(&self, origin: Vec2, size: f32, resolution: u32) -> Mesh {
+        let step = size / resolution as f32;
+        let count = resolution + 1;
+
+        let mut positions = Vec::with_capacity((count * count) as usize);
+        let mut normals = Vec::with_capacity((count * count) as usize);
+        let mut uvs = Vec::with_capacity((count * count) as usize);
+
+        for z in 0..count {
+            for x in 0..count {
+                let local = Vec2::new(x as f32, z as f32) * step;
+                let world = origin + local;
+
+                positions.push([local.x, self.height_at(world), local.y]);
+                normals.push(self.normal_at(world, step).to_array());
+                uvs.push(self.to_uv(world).to_array());
+            }
+        }
+
+        // quads are split by the same diagonal as heightfield's cells,
+        // so collider is on the surface of mesh
+        let mut indices = Vec::with_capacity((resolution * resolution * 6) as usize);
+        for z in 0..resolution {
+            for x in 0..resolution {
+                let a = z * count + x;
+                let (b, c, d) = (a + count, a + 1, a + count + 1);
+                indices.extend_from_slice(&[a, b, d, a, d, c]);
+            }
+        }
+
+        Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default())
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+            .with_inserted_indices(Indices::U32(indices))
+    }
+
+    /// Build heightfield collider of chunk, it's centered in chunk
+    pub fn chunk_collider(&self, origin: Vec2, size: f32, resolution: u32) -> Collider {
+        let step = size / resolution as f32;
+        let count = (resolution + 1) as usize;
+
+        // rows are along Z, columns are along X (column-major)
+        let mut heights = Vec::with_capacity(count * count);
+        for x in 0..count {
+            for z in 0..count {
+                heights.push(self.height_at(origin + Vec2::new(x as f32, z as f32) * step));
+            }
+        }
+
+        Collider::heightfield(heights, count, count, Vec3::new(size, 1.0, size))
+    }
+
+    /// Convert point (x, z) of world to uv of terrain
+    #[inline]
+    fn to_uv(&self, position: Vec2) -> Vec2 {
+        (position / self.size + 0.5).clamp(Vec2::ZERO, Vec2::ONE)
+    }
+
+    /// Convert point (x, z) of world to pixel of image with size
+    #[inline]
+    fn to_pixel(&self, position: Vec2, image_size: UVec2) -> Vec2 {
+        self.to_uv(position) * (image_size - 1).as_vec2()
+    }
+}
+
+/// State of terrain
+#[derive(Resource)]
+pub struct Terrain {
+    pub heightmap: Handle<Image>,
+    pub splat_map: Handle<Image>,
+    /// data is read when images are loaded
+    pub data: Option<TerrainData>,
+    pub material: Option<Handle<TerrainMaterial>>,
+}
+
+impl Terrain {
+    /// Return height in point (x, z) of world, without terrain it's zero
+    pub fn height_at(&self, position: Vec2) -> f32 {
+        self.data.as_ref().map(|data| data.height_at(position)).unwrap_or(0.0)
+    }
+
+    /// Return surface in point (x, z) of world
+    pub fn surface_at(&self, position: Vec2) -> SurfaceType {
+        self.data.as_ref().map(|data| data.surface_at(position)).unwrap_or_default()
+    }
+}
+
+/// Mesh of terrain's chunk
+#[derive(Component)]
+pub struct TerrainChunk {
+    /// meshes of LOD levels
+    pub lods: [Handle<Mesh>; TERRAIN_LODS],
+    /// current LOD level
+    pub lod: usize,
+}
+
+/// Collider of terrain, it's used to find surface of ground
+#[derive(Component)]
+pub struct TerrainCollider;
+
+/// Terrain is built for forest chunk
+#[derive(Component)]
+pub struct TerrainBuilt;
+
+#[cfg(test)]
+mod tests {
+    use bevy::render::mesh::VertexAttributeValues;
+
+    use super::*;
+
+    const SIZE: f32 = 8.0;
+    const RESOLUTION: u32 = 4;
+
+    /// terrain 16x16 with bumpy heightmap, pixel is on every vertex of chunk
+    fn terrain() -> TerrainData {
+        TerrainData {
+            size: SIZE * 2.0,
+            heights: (0..81).map(|index| (index * 7919 % 13) as f32 * 0.5).collect(),
+            heights_size: UVec2::splat(9),
+            splat: vec![[255, 0, 0, 0]],
+            splat_size: UVec2::ONE,
+            surfaces: [SurfaceType::Grass; 4],
+        }
+    }
+
+    /// height of mesh's triangle under point (x, z) of chunk
+    fn mesh_height(mesh: &Mesh, point: Vec2) -> Option<f32> {
+        let Some(VertexAttributeValues::Float32x3(positions)) = mesh.attribute(Mesh::ATTRIBUTE_POSITION) else {
+            return None;
+        };
+        let Some(Indices::U32(indices)) = mesh.indices() else { return None; };
+
+        indices.chunks_exact(3).find_map(|triangle| {
+            let [a, b, c] = [0, 1, 2].map(|vertex| Vec3::from_array(positions[triangle[vertex] as usize]));
+            let area = (b.xz() - a.xz()).perp_dot(c.xz() - a.xz());
+
+            // barycentric weights of vertices
+            let u = (b.xz() - point).perp_dot(c.xz() - point) / area;
+            let v = (c.xz() - point).perp_dot(a.xz() - point) / area;
+            let w = 1.0 - u - v;
+
+            (u >= -1e-4 && v >= -1e-4 && w >= -1e-4).then_some(a.y * u + b.y * v + c.y * w)
+        })
+    }
+
+    /// height of collider under point (x, z) of chunk
+    fn collider_height(collider: &Collider, point: Vec2) -> Option<f32> {
+        // collider is centered in chunk
+        let origin = Vec3::new(point.x - SIZE / 2.0, 100.0, point.y - SIZE / 2.0);
+        collider.cast_local_ray(origin, Vec3::NEG_Y, 200.0, true).map(|distance| origin.y - distance)
+    }
+
+    #[test]
+    fn mesh_and_collider_have_same_heights() {
+        let data = terrain();
+        let origin = Vec2::splat(-SIZE);
+        let mesh = data.chunk_mesh(origin, SIZE, RESOLUTION);
+        let collider = data.chunk_collider(origin, SIZE, RESOLUTION);
+
+        let step = SIZE / RESOLUTION as f32;
+        for z in 0..RESOLUTION {
+            for x in 0..RESOLUTION {
+                // near vertex, center and both sides of cell's diagonals
+                for offset in [[0.01, 0.01], [0.5, 0.5], [0.25, 0.75], [0.75, 0.25], [0.2, 0.4], [0.9, 0.6]] {
+                    let point = (Vec2::new(x as f32, z as f32) + Vec2::from_array(offset)) * step;
+
+                    let mesh_y = mesh_height(&mesh, point).expect("point is outside of mesh");
+                    let collider_y = collider_height(&collider, point).expect("point is outside of collider");
+                    assert!((mesh_y - collider_y).abs() < 1e-3, "heights differ in {point}: mesh {mesh_y}, collider {collider_y}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn mesh_vertices_are_on_heightmap() {
+        let data = terrain();
+        let origin = Vec2::splat(-SIZE);
+        let mesh = data.chunk_mesh(origin, SIZE, RESOLUTION);
+
+        let step = SIZE / RESOLUTION as f32;
+        for z in 0..=RESOLUTION {
+            for x in 0..=RESOLUTION {
+                let point = Vec2::new(x as f32, z as f32) * step;
+                let height = mesh_height(&mesh, point).expect("point is outside of mesh");
+                assert!((height - data.height_at(origin + point)).abs() < 1e-3);
+            }
+        }
+    }
+}
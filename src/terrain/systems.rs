@@ -0,0 +1,143 @@
+//! implementation of terrain systems
+
+// import crates
+use bevy::{image::ImageLoaderSettings, prelude::*};
+
+// import data from this crate
+use crate::levels::structures::ForestChunk;
+use super::structures::*;
+
+/// start loading of terrain's images
+pub fn load_terrain(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    // weights of splat map aren't colors
+    let splat_map = asset_server.load_with_settings(SPLAT_MAP, |settings: &mut ImageLoaderSettings| {
+        settings.is_srgb = false;
+    });
+
+    commands.insert_resource(Terrain {
+        heightmap: asset_server.load(HEIGHTMAP),
+        splat_map,
+        data: None,
+        material: None,
+    });
+}
+
+/// read heights and weights, when images are loaded
+pub fn prepare_terrain(
+    mut materials: ResMut<Assets<TerrainMaterial>>,
+    mut terrain: ResMut<Terrain>,
+    settings: Res<TerrainSettings>,
+    images: Res<Assets<Image>>,
+) {
+    if terrain.data.is_some() {
+        return;
+    }
+
+    let (Some(heightmap), Some(splat_map)) = (images.get(&terrain.heightmap), images.get(&terrain.splat_map)) else {
+        return;
+    };
+
+    // heightmap can be 8 or 16 bit, only first channel is used
+    let heights_size = heightmap.size();
+    let pixel_size = heightmap.data.len() / (heights_size.x * heights_size.y) as usize;
+    let heights = heightmap.data.chunks_exact(pixel_size)
+        .map(|pixel| match pixel_size {
+            2 => u16::from_le_bytes([pixel[0], pixel[1]]) as f32 / u16::MAX as f32,
+            _ => pixel[0] as f32 / u8::MAX as f32,
+        } * settings.max_height)
+        .collect();
+
+    let splat_size = splat_map.size();
+    let splat = splat_map.data.chunks_exact(4)
+        .map(|pixel| [pixel[0], pixel[1], pixel[2], pixel[3]])
+        .collect();
+
+    terrain.data = Some(TerrainData {
+        size: settings.size,
+        heights,
+        heights_size,
+        splat,
+        splat_size,
+        surfaces: settings.layer_surfaces,
+    });
+
+    terrain.material = Some(materials.add(TerrainMaterial {
+        base: StandardMaterial {
+            perceptual_roughness: 0.95,
+            ..default()
+        },
+        extension: TerrainSplat {
+            layer_colors: settings.layer_colors.map(|color| color.to_linear().to_vec4()),
+            splat_map: terrain.splat_map.clone(),
+        },
+    }));
+}
+
+/// build meshes and colliders of terrain for new forest chunks
+pub fn build_terrain_chunks(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    chunks_query: Query<(Entity, &ForestChunk), Without<TerrainBuilt>>,
+    settings: Res<TerrainSettings>,
+    terrain: Res<Terrain>,
+) {
+    let (Some(data), Some(material)) = (&terrain.data, &terrain.material) else { return; };
+
+    for (entity, chunk) in chunks_query.iter() {
+        let origin = chunk.coord.as_vec2() * chunk.size;
+
+        let lods = std::array::from_fn(|lod| {
+            let resolution = (settings.resolution >> lod).max(1);
+            meshes.add(data.chunk_mesh(origin, chunk.size, resolution))
+        });
+
+        commands.entity(entity).insert(TerrainBuilt).with_children(|parent| {
+            parent.spawn((
+                #[cfg(debug_assertions)] Name::new("Terrain"),
+                Mesh3d(lods[0].clone()),
+                MeshMaterial3d(material.clone()),
+                Transform::default(),
+                TerrainChunk { lods, lod: 0 },
+            ));
+
+            parent.spawn((
+                #[cfg(debug_assertions)] Name::new("Terrain collider"),
+                Transform::from_xyz(chunk.size / 2.0, 0.0, chunk.size / 2.0),
+                data.chunk_collider(origin, chunk.size, settings.resolution),
+                TerrainCollider,
+            ));
+        });
+    }
+}
+
+/// change LOD of terrain's chunks by distance to camera
+pub fn update_terrain_lod(
+    mut chunks_query: Query<(&mut TerrainChunk, &mut Mesh3d, &GlobalTransform, &Parent)>,
+    cameras_query: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    forest_chunks: Query<&ForestChunk>,
+    settings: Res<TerrainSettings>,
+) {
+    let Some(camera_position) = cameras_query.iter()
+        .find(|(camera, _)| camera.is_active)
+        .map(|(_, transform)| transform.translation())
+    else {
+        return;
+    };
+
+    for (mut chunk, mut mesh, transform, parent) in chunks_query.iter_mut() {
+        let Ok(forest_chunk) = forest_chunks.get(parent.get()) else { continue; };
+
+        // distance to center of chunk
+        let center = transform.translation() + Vec3::new(forest_chunk.size, 0.0, forest_chunk.size) / 2.0;
+        let distance = center.xz().distance(camera_position.xz());
+
+        let lod = settings.lod_distances.iter().filter(|max| distance > **max).count();
+        if chunk.lod != lod {
+            chunk.lod = lod;
+            mesh.0 = chunk.lods[lod].clone();
+        }
+    }
+}
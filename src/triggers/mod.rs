@@ -0,0 +1,27 @@
+//! Triggers - region volumes (rapier sensors) which fire events
+//! with action id, when player or monster enters or exits them.
+//! Volumes are authored in blender via bevy_skein.
+
+// import crates
+use bevy::prelude::*;
+
+// trigger systems
+pub(super) mod systems;
+pub mod structures;
+
+pub struct TriggersPlugin;
+impl Plugin for TriggersPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<structures::TriggerVolume>();
+        app.register_type::<structures::TriggerShape>();
+        app.register_type::<structures::TriggerFilter>();
+
+        app.add_event::<structures::TriggerEntered>();
+        app.add_event::<structures::TriggerExited>();
+
+        app.add_systems(Update, (
+            systems::setup_trigger_volumes,
+            systems::dispatch_trigger_events,
+        ).chain());
+    }
+}
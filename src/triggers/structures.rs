@@ -0,0 +1,80 @@
+//! This module store trigger's components and events
+use bevy::prelude::*;
+use educe::Educe;
+
+/// Shape of trigger volume (it's scaled by entity's scale)
+#[derive(Clone, Copy, Debug, Reflect)]
+pub enum TriggerShape {
+    /// box with half size
+    Box { half_extents: Vec3 },
+    /// sphere with radius
+    Sphere { radius: f32 },
+}
+
+impl Default for TriggerShape {
+    fn default() -> Self {
+        TriggerShape::Box { half_extents: Vec3::ONE }
+    }
+}
+
+/// Who can activate trigger
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum TriggerFilter {
+    #[default]
+    Player,
+    Monster,
+    Any,
+}
+
+/// Region trigger, it fires `TriggerEntered` and `TriggerExited`
+/// with action id (for example "sequence:sequences/first_encounter.sequence.ron")
+#[derive(Component, Clone, Debug, Educe, Reflect)]
+#[reflect(Component)]
+#[educe(Default)]
+#[require(Transform)]
+pub struct TriggerVolume {
+    /// id of action
+    pub action: String,
+
+    /// shape of volume
+    pub shape: TriggerShape,
+
+    #[educe(Default = false)] /// trigger fires every time, else only once
+    pub repeatable: bool,
+
+    #[educe(Default = TriggerFilter::Player)] /// who can activate trigger
+    pub filter: TriggerFilter,
+}
+
+/// Marker of one-shot trigger which already fired
+#[derive(Component)]
+pub struct TriggerFired;
+
+/// Event: entity entered trigger volume
+#[derive(Event, Clone, Debug)]
+pub struct TriggerEntered {
+    /// entity of trigger
+    pub trigger: Entity,
+    /// entity which entered
+    pub other: Entity,
+    /// id of action
+    pub action: String,
+}
+
+/// Event: entity exited trigger volume
+#[derive(Event, Clone, Debug)]
+pub struct TriggerExited {
+    /// entity of trigger
+    pub trigger: Entity,
+    /// entity which exited
+    pub other: Entity,
+    /// id of action
+    pub action: String,
+}
+
+impl TriggerEntered {
+    /// Return argument of action if action has prefix ("prefix:argument")
+    pub fn argument(&self, prefix: &str) -> Option<&str> {
+        self.action.strip_prefix(prefix)?.strip_prefix(':')
+    }
+}
@@ -0,0 +1,87 @@
+//! implementation of trigger systems
+
+// import crates
+use bevy::prelude::*;
+use bevy_rapier3d::prelude::*;
+
+// import data from this crate
+use crate::components::Monster;
+use crate::player::structures::PlayerComponent;
+use super::structures::*;
+
+/// add sensor collider to new trigger volumes
+pub fn setup_trigger_volumes(
+    mut commands: Commands,
+    triggers_query: Query<(Entity, &TriggerVolume), Added<TriggerVolume>>,
+) {
+    for (entity, trigger) in triggers_query.iter() {
+        let collider = match trigger.shape {
+            TriggerShape::Box { half_extents } => Collider::cuboid(half_extents.x, half_extents.y, half_extents.z),
+            TriggerShape::Sphere { radius } => Collider::ball(radius),
+        };
+
+        // player is kinematic body, so sensor must react on it
+        commands.entity(entity).insert((
+            collider,
+            Sensor,
+            ActiveEvents::COLLISION_EVENTS,
+            ActiveCollisionTypes::default() | ActiveCollisionTypes::KINEMATIC_STATIC,
+        ));
+    }
+}
+
+/// convert collision events of sensors to trigger events
+pub fn dispatch_trigger_events(
+    mut commands: Commands,
+    mut collisions: EventReader<CollisionEvent>,
+    mut entered: EventWriter<TriggerEntered>,
+    mut exited: EventWriter<TriggerExited>,
+    triggers_query: Query<(&TriggerVolume, Has<TriggerFired>)>,
+    players_query: Query<(), With<PlayerComponent>>,
+    monsters_query: Query<(), With<Monster>>,
+    parents: Query<&Parent>,
+) {
+    for event in collisions.read() {
+        let (first, second, started) = match event {
+            CollisionEvent::Started(first, second, _) => (*first, *second, true),
+            CollisionEvent::Stopped(first, second, _) => (*first, *second, false),
+        };
+
+        // find which entity is trigger
+        let (trigger_entity, other) = if triggers_query.contains(first) {
+            (first, second)
+        } else if triggers_query.contains(second) {
+            (second, first)
+        } else {
+            continue;
+        };
+
+        let Ok((trigger, fired)) = triggers_query.get(trigger_entity) else { continue; };
+
+        // collider of monster can be on child entity
+        let is_player = players_query.contains(other);
+        let is_monster = std::iter::once(other)
+            .chain(parents.iter_ancestors(other))
+            .any(|entity| monsters_query.contains(entity));
+
+        let passes = match trigger.filter {
+            TriggerFilter::Player => is_player,
+            TriggerFilter::Monster => is_monster,
+            TriggerFilter::Any => true,
+        };
+
+        if !passes || fired {
+            continue;
+        }
+
+        if started {
+            entered.send(TriggerEntered { trigger: trigger_entity, other, action: trigger.action.clone() });
+
+            if !trigger.repeatable {
+                commands.entity(trigger_entity).insert(TriggerFired);
+            }
+        } else {
+            exited.send(TriggerExited { trigger: trigger_entity, other, action: trigger.action.clone() });
+        }
+    }
+}
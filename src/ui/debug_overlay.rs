@@ -0,0 +1,125 @@
+//! Developer overlay - raw debug data of player and fps,
+//! it's compiled only in debug builds and toggled by F3
+use bevy::{color::palettes::css::GOLD, diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin}, prelude::*};
+
+use crate::localization::structures::Localized;
+use crate::player::structures::{PlayerComponent, PlayerControllerData};
+use crate::vegetation::structures::VegetationStats;
+
+#[derive(Component)] /// root of developer overlay
+pub struct DebugOverlay;
+
+#[derive(Component)] /// label of fps text
+pub struct FpsText;
+
+#[derive(Component)] /// label of player's text
+pub struct PlayerDataText;
+
+#[derive(Component)] /// label of vegetation's text
+pub struct VegetationText;
+
+/// setup developer overlay
+pub fn setup_debug_overlay(
+    mut commands: Commands,
+) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        PickingBehavior::IGNORE,
+        DebugOverlay,
+    )).with_children(|parent| {
+        // add fps text
+        parent.spawn((
+            TextFont { font_size: 42.0, ..default() },
+            TextColor(GOLD.into()),
+            Text::new(""),
+            Localized::new("hud-fps"),
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(100.0),
+                left: Val::Px(10.0),
+                ..default()
+            },
+        )).with_child((TextSpan::default(), FpsText));
+
+        // add text of vegetation statistics
+        parent.spawn((
+            TextFont { font_size: 24.0, ..default() },
+            Text::new(""),
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(150.0),
+                left: Val::Px(10.0),
+                ..default()
+            },
+        )).with_child((TextSpan::default(), VegetationText));
+
+        // add text of player data
+        parent.spawn((
+            TextFont { font_size: 30.0, ..default() },
+            Text::new(""),
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(15.0),
+                left: Val::Px(5.0),
+                ..Default::default()
+            }
+        )).with_child((TextSpan::default(), PlayerDataText));
+    });
+}
+
+/// show and hide overlay by F3
+pub fn toggle_debug_overlay(
+    mut overlay_query: Query<&mut Visibility, With<DebugOverlay>>,
+    keys: Res<ButtonInput<KeyCode>>,
+) {
+    if !keys.just_pressed(KeyCode::F3) {
+        return;
+    }
+
+    for mut visibility in overlay_query.iter_mut() {
+        *visibility = match *visibility {
+            Visibility::Hidden => Visibility::Inherited,
+            _ => Visibility::Hidden,
+        };
+    }
+}
+
+/// update text for debug
+pub fn update_debug_overlay(
+    diagnostics: Res<DiagnosticsStore>,
+    player_data_query: Query<(&PlayerComponent, &PlayerControllerData)>,
+    mut fps_text_query: Query<&mut TextSpan, (With<FpsText>, Without<PlayerDataText>)>,
+    mut player_text_query: Query<&mut TextSpan, (With<PlayerDataText>, Without<FpsText>)>,
+    mut vegetation_text_query: Query<&mut TextSpan, (With<VegetationText>, Without<FpsText>, Without<PlayerDataText>)>,
+    vegetation: Res<VegetationStats>,
+) {
+    for mut span in &mut fps_text_query {
+        if let Some(fps) = diagnostics.get(&FrameTimeDiagnosticsPlugin::FPS) {
+            if let Some(value) = fps.smoothed() {
+                **span = format!("{value:.2}");
+            }
+        }
+    }
+
+    // player can be absent (for example between levels)
+    let player_text = match player_data_query.get_single() {
+        Ok((player, controller)) => format!("{:?}\n{:?}", controller, player),
+        Err(_) => "no player".into(),
+    };
+
+    for mut span in &mut player_text_query {
+        **span = player_text.clone();
+    }
+
+    for mut span in &mut vegetation_text_query {
+        **span = format!(
+            "vegetation: {} / {} instances, {} batches",
+            vegetation.drawn, vegetation.instances, vegetation.batches
+        );
+    }
+}
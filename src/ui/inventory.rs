@@ -0,0 +1,174 @@
+//! Inventory - screen with collected items (toggled by Tab),
+//! journal can be opened from it
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::components::{InInventory, Item};
+use crate::localization::structures::Localized;
+use crate::objectives::structures::{JournalState, OpenJournal};
+use crate::player::structures::{InputOwner, InputOwners, PlayerInputEnabled, SavedInput};
+
+/// State of inventory
+#[derive(Resource, Default)]
+pub struct InventoryState {
+    /// inventory is open
+    pub open: bool,
+    /// input and cursor before inventory opened
+    pub saved: SavedInput,
+}
+
+#[derive(Component)] /// root of inventory
+pub struct Inventory;
+
+#[derive(Component)] /// list of items in inventory
+pub struct InventoryItems;
+
+#[derive(Component)] /// button which opens journal
+pub struct JournalButton;
+
+/// setup hidden inventory screen
+pub fn setup_inventory(
+    mut commands: Commands,
+) {
+    commands.spawn((
+        #[cfg(debug_assertions)] Name::new("Inventory"),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        GlobalZIndex(4),
+        Visibility::Hidden,
+        Inventory,
+    )).with_children(|parent| {
+        parent.spawn((
+            Node {
+                width: Val::Px(360.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(6.0),
+                padding: UiRect::all(Val::Px(20.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.12, 0.11, 0.1)),
+        )).with_children(|panel| {
+            panel.spawn((
+                TextFont { font_size: 28.0, ..default() },
+                Text::new(""),
+                Localized::new("inventory-title"),
+            ));
+
+            panel.spawn((
+                Node {
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(4.0),
+                    ..default()
+                },
+                InventoryItems,
+            ));
+
+            panel.spawn((
+                Button,
+                Node {
+                    margin: UiRect::top(Val::Px(12.0)),
+                    padding: UiRect::axes(Val::Px(10.0), Val::Px(4.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.2, 0.19, 0.17)),
+                JournalButton,
+            )).with_child((
+                TextFont { font_size: 22.0, ..default() },
+                Text::new(""),
+                Localized::new("inventory-journal"),
+            ));
+        });
+    });
+}
+
+/// open and close inventory by Tab, while inventory is
+/// open cursor is free and player's input is disabled
+pub fn toggle_inventory(
+    mut inventory_query: Query<&mut Visibility, With<Inventory>>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+    mut input_enabled: ResMut<PlayerInputEnabled>,
+    mut inventory: ResMut<InventoryState>,
+    mut input_owners: ResMut<InputOwners>,
+    journal: Res<JournalState>,
+    keys: Res<ButtonInput<KeyCode>>,
+) {
+    if !keys.just_pressed(KeyCode::Tab) {
+        return;
+    }
+
+    if inventory.open {
+        // journal opened from inventory is closed first
+        if journal.open {
+            return;
+        }
+
+        inventory.saved.restore(&mut input_enabled, window_query.get_single_mut().ok());
+        input_owners.0.remove(&InputOwner::Inventory);
+    } else {
+        // inventory is opened only from game, else other screen owns input
+        if !input_enabled.0 {
+            return;
+        }
+
+        inventory.saved = SavedInput::take(&mut input_enabled, window_query.get_single_mut().ok());
+        input_owners.0.insert(InputOwner::Inventory);
+    }
+
+    inventory.open = !inventory.open;
+
+    for mut visibility in inventory_query.iter_mut() {
+        *visibility = if inventory.open { Visibility::Inherited } else { Visibility::Hidden };
+    }
+}
+
+/// rebuild list of items when inventory is opened
+pub fn update_inventory_items(
+    mut commands: Commands,
+    list_query: Query<Entity, With<InventoryItems>>,
+    items_query: Query<&Item, With<InInventory>>,
+    inventory: Res<InventoryState>,
+) {
+    if !inventory.is_changed() || !inventory.open {
+        return;
+    }
+
+    for list in list_query.iter() {
+        commands.entity(list).despawn_descendants().with_children(|parent| {
+            if items_query.is_empty() {
+                parent.spawn((
+                    TextFont { font_size: 20.0, ..default() },
+                    TextColor(Color::srgb(0.6, 0.58, 0.55)),
+                    Text::new(""),
+                    Localized::new("inventory-empty"),
+                ));
+            }
+
+            for item in items_query.iter() {
+                parent.spawn((
+                    TextFont { font_size: 20.0, ..default() },
+                    Text::new(""),
+                    Localized::new(item.name.clone()),
+                ));
+            }
+        });
+    }
+}
+
+/// open journal by inventory's button
+pub fn press_journal_button(
+    buttons_query: Query<&Interaction, (Changed<Interaction>, With<JournalButton>)>,
+    mut open_journal: EventWriter<OpenJournal>,
+) {
+    for interaction in buttons_query.iter() {
+        if *interaction == Interaction::Pressed {
+            open_journal.send(OpenJournal);
+        }
+    }
+}
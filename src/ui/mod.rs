@@ -0,0 +1,45 @@
+//! UI - this a module for GUI systems: gameplay HUD, inventory
+//! and developer overlay (only in debug builds)
+
+// import crates
+use bevy::prelude::*;
+
+use crate::components::HotkeySystems;
+
+// gui systems
+pub(super) mod systems;
+pub(super) mod inventory;
+pub mod structures;
+
+#[cfg(debug_assertions)]
+pub(super) mod debug_overlay;
+
+pub struct UiPlugin;
+impl Plugin for UiPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(inventory::InventoryState::default());
+
+        app.add_systems(Startup, (systems::setup_hud, inventory::setup_inventory));
+        app.add_systems(Update, (
+            systems::update_stamina_bar,
+            systems::update_fear_indicator,
+            systems::update_battery_bar,
+            systems::update_interaction_prompt,
+            systems::spawn_toasts,
+            systems::update_toasts,
+        ));
+        app.add_systems(Update, (
+            inventory::toggle_inventory.in_set(HotkeySystems),
+            inventory::update_inventory_items,
+            inventory::press_journal_button,
+        ).chain());
+
+        #[cfg(debug_assertions)] {
+            app.add_systems(Startup, debug_overlay::setup_debug_overlay);
+            app.add_systems(Update, (
+                debug_overlay::toggle_debug_overlay.in_set(HotkeySystems),
+                debug_overlay::update_debug_overlay,
+            ));
+        }
+    }
+}
@@ -0,0 +1,41 @@
+//! This module store HUD's components and consts
+use bevy::prelude::*;
+
+// const's
+/// time (in seconds) after which full stamina bar starts to fade
+pub(super) const STAMINA_FADE_DELAY: f32 = 1.5;
+/// speed of stamina bar fade (alpha per second)
+pub(super) const STAMINA_FADE_SPEED: f32 = 1.5;
+/// maximal alpha of fear vignette
+pub(super) const FEAR_MAX_ALPHA: f32 = 0.35;
+/// fear at which vignette is maximal
+pub(super) const FEAR_MAX: f32 = 100.0;
+/// how long toast is shown (in seconds)
+pub(super) const TOAST_LIFETIME: f32 = 3.0;
+
+#[derive(Component)] /// root node of stamina bar
+pub struct StaminaBar {
+    /// how long (in seconds) stamina is full
+    pub full_for: f32,
+}
+
+#[derive(Component)] /// fill of stamina bar
+pub struct StaminaFill;
+
+#[derive(Component)] /// red vignette, which shows player's fear
+pub struct FearIndicator;
+
+#[derive(Component)] /// root node of flashlight battery bar
+pub struct BatteryBar;
+
+#[derive(Component)] /// fill of battery bar
+pub struct BatteryFill;
+
+#[derive(Component)] /// text of interaction prompt
+pub struct InteractionPrompt;
+
+#[derive(Component)] /// column of toasts
+pub struct ToastContainer;
+
+#[derive(Component)] /// notification about picked item
+pub struct Toast(pub Timer);
@@ -0,0 +1,251 @@
+//! implementation of HUD systems
+
+// import crates
+use bevy::prelude::*;
+
+// import data from this crate
+use crate::interaction::{Interactable, InteractionFocus, ItemPickedUp, INTERACTION_KEY_NAME};
+use crate::localization::structures::Localized;
+use crate::player::structures::{Flashlight, PlayerComponent, MAX_STAMINA};
+use super::structures::*;
+
+/// setup HUD interface
+pub fn setup_hud(
+    mut commands: Commands,
+) {
+    // add fear vignette
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.5, 0.0, 0.0, 0.0)),
+        PickingBehavior::IGNORE,
+        FearIndicator,
+    ));
+
+    // add stamina bar
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(30.0),
+            left: Val::Percent(40.0),
+            width: Val::Percent(20.0),
+            height: Val::Px(6.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.6)),
+        PickingBehavior::IGNORE,
+        StaminaBar { full_for: 0.0 },
+    )).with_child((
+        Node { width: Val::Percent(100.0), height: Val::Percent(100.0), ..default() },
+        BackgroundColor(Color::srgb(0.85, 0.85, 0.8)),
+        StaminaFill,
+    ));
+
+    // add battery bar
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(30.0),
+            right: Val::Px(30.0),
+            width: Val::Px(80.0),
+            height: Val::Px(12.0),
+            padding: UiRect::all(Val::Px(2.0)),
+            border: UiRect::all(Val::Px(1.0)),
+            ..default()
+        },
+        BorderColor(Color::srgb(0.7, 0.7, 0.7)),
+        Visibility::Hidden,
+        PickingBehavior::IGNORE,
+        BatteryBar,
+    )).with_child((
+        Node { width: Val::Percent(100.0), height: Val::Percent(100.0), ..default() },
+        BackgroundColor(Color::srgb(0.9, 0.8, 0.3)),
+        BatteryFill,
+    ));
+
+    // add center point and interaction prompt
+    commands
+        .spawn(Node {
+            width: Val::Percent(100.),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            flex_direction: FlexDirection::Column,
+            ..Default::default()
+        })
+        .insert(PickingBehavior::IGNORE)
+        .with_children(|parent| {
+            parent.spawn(Node {
+                    width: Val::Px(8.0),
+                    height: Val::Px(8.0),
+                    margin: UiRect::bottom(Val::Px(4.0)),
+                    ..Default::default()
+                }
+            ).insert(BackgroundColor(Color::srgb(235.0, 35.0, 12.0)));
+
+            parent.spawn((
+                Node { margin: UiRect::top(Val::Px(40.0)), ..default() },
+                TextFont { font_size: 24.0, ..default() },
+                Text::new(""),
+                Localized::default(),
+                Visibility::Hidden,
+                InteractionPrompt,
+            ));
+        }
+    );
+
+    // add toasts column
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(20.0),
+            right: Val::Px(20.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::FlexEnd,
+            row_gap: Val::Px(6.0),
+            ..default()
+        },
+        PickingBehavior::IGNORE,
+        ToastContainer,
+    ));
+}
+
+/// update stamina bar, it fades when stamina is full
+pub fn update_stamina_bar(
+    mut bar_query: Query<(&mut StaminaBar, &mut Visibility, &mut BackgroundColor), Without<StaminaFill>>,
+    mut fill_query: Query<(&mut Node, &mut BackgroundColor), With<StaminaFill>>,
+    player_query: Query<&PlayerComponent>,
+    time: Res<Time>,
+) {
+    let Ok((mut bar, mut visibility, mut bar_color)) = bar_query.get_single_mut() else { return; };
+    let Ok((mut fill_node, mut fill_color)) = fill_query.get_single_mut() else { return; };
+
+    // without player bar is hidden
+    let Ok(player) = player_query.get_single() else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+    *visibility = Visibility::Inherited;
+
+    let ratio = (player.stamina / MAX_STAMINA).clamp(0.0, 1.0);
+    fill_node.width = Val::Percent(ratio * 100.0);
+
+    // fade bar after stamina is full for some time
+    if ratio >= 1.0 {
+        bar.full_for += time.delta_secs();
+    } else {
+        bar.full_for = 0.0;
+    }
+
+    let alpha = (1.0 - (bar.full_for - STAMINA_FADE_DELAY).max(0.0) * STAMINA_FADE_SPEED).clamp(0.0, 1.0);
+
+    // red fill when player can't run
+    let fill = if player.stop_run { Color::srgb(0.8, 0.2, 0.15) } else { Color::srgb(0.85, 0.85, 0.8) };
+    fill_color.0 = fill.with_alpha(alpha);
+    bar_color.0 = bar_color.0.with_alpha(alpha * 0.6);
+}
+
+/// update fear vignette
+pub fn update_fear_indicator(
+    mut indicator_query: Query<&mut BackgroundColor, With<FearIndicator>>,
+    player_query: Query<&PlayerComponent>,
+) {
+    let fear = player_query.get_single().map(|player| player.fear).unwrap_or(0.0);
+    let alpha = (fear / FEAR_MAX).clamp(0.0, 1.0) * FEAR_MAX_ALPHA;
+
+    for mut color in indicator_query.iter_mut() {
+        color.0 = color.0.with_alpha(alpha);
+    }
+}
+
+/// update flashlight battery bar
+pub fn update_battery_bar(
+    mut bar_query: Query<&mut Visibility, With<BatteryBar>>,
+    mut fill_query: Query<&mut Node, With<BatteryFill>>,
+    flashlight_query: Query<&Flashlight>,
+) {
+    let flashlight = flashlight_query.get_single().ok();
+
+    for mut visibility in bar_query.iter_mut() {
+        *visibility = if flashlight.is_some() { Visibility::Inherited } else { Visibility::Hidden };
+    }
+
+    if let Some(flashlight) = flashlight {
+        for mut node in fill_query.iter_mut() {
+            node.width = Val::Percent(flashlight.battery.clamp(0.0, 1.0) * 100.0);
+        }
+    }
+}
+
+/// show prompt of interactable object at which player looks
+pub fn update_interaction_prompt(
+    mut prompt_query: Query<(&mut Localized, &mut Visibility), With<InteractionPrompt>>,
+    interactables: Query<&Interactable>,
+    focus: Res<InteractionFocus>,
+) {
+    if !focus.is_changed() {
+        return;
+    }
+
+    let interactable = focus.0.and_then(|entity| interactables.get(entity).ok());
+
+    for (mut localized, mut visibility) in prompt_query.iter_mut() {
+        match interactable {
+            Some(interactable) => {
+                *localized = Localized::new("interaction-prompt")
+                    .with_arg("key", INTERACTION_KEY_NAME)
+                    .with_localized_arg("action", &interactable.prompt);
+                *visibility = Visibility::Inherited;
+            },
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+}
+
+/// show toast for every picked item
+pub fn spawn_toasts(
+    mut commands: Commands,
+    mut picked: EventReader<ItemPickedUp>,
+    container_query: Query<Entity, With<ToastContainer>>,
+) {
+    let Ok(container) = container_query.get_single() else { return; };
+
+    for item in picked.read() {
+        commands.entity(container).with_child((
+            Node {
+                padding: UiRect::axes(Val::Px(10.0), Val::Px(4.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            TextFont { font_size: 22.0, ..default() },
+            Text::new(""),
+            Localized::new("toast-picked-up").with_localized_arg("item", &item.name),
+            Toast(Timer::from_seconds(TOAST_LIFETIME, TimerMode::Once)),
+        ));
+    }
+}
+
+/// fade and despawn old toasts
+pub fn update_toasts(
+    mut commands: Commands,
+    mut toasts_query: Query<(Entity, &mut Toast, &mut TextColor, &mut BackgroundColor)>,
+    time: Res<Time>,
+) {
+    for (entity, mut toast, mut text_color, mut background) in toasts_query.iter_mut() {
+        toast.0.tick(time.delta());
+
+        if toast.0.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        // fade in last second
+        let alpha = toast.0.remaining_secs().min(1.0);
+        text_color.0 = text_color.0.with_alpha(alpha);
+        background.0 = background.0.with_alpha(alpha * 0.6);
+    }
+}
@@ -0,0 +1,28 @@
+//! Vegetation - trees and undergrowth, which are drawn by instancing:
+//! one draw call for every layer and LOD of forest chunk.
+//! Instances are culled and faded by distance, chunks by frustum.
+
+// import crates
+use bevy::prelude::*;
+
+// vegetation systems
+pub(super) mod systems;
+pub(super) mod render;
+pub mod structures;
+
+pub struct VegetationPlugin;
+impl Plugin for VegetationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(render::VegetationRenderPlugin);
+
+        app.register_type::<structures::VegetationSettings>();
+        app.insert_resource(structures::VegetationSettings::default());
+        app.insert_resource(structures::VegetationStats::default());
+
+        app.add_systems(Startup, systems::setup_vegetation_assets);
+        app.add_systems(Update, (
+            systems::setup_vegetation_batches,
+            systems::update_vegetation_batches,
+        ).chain().after(crate::forest::systems::generate_forest_chunks));
+    }
+}
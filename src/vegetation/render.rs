@@ -0,0 +1,394 @@
+//! Render pipeline of vegetation. It draws mesh of batch once
+//! for every instance, instances are sent to GPU as vertex buffer.
+//! Batches are drawn to main pass and to shadow maps of lights.
+use bevy::{
+    core_pipeline::{
+        core_3d::{Transparent3d, CORE_3D_DEPTH_FORMAT},
+        tonemapping::{DebandDither, Tonemapping},
+    },
+    ecs::{query::QueryItem, system::{lifetimeless::{Read, SRes}, SystemParamItem}},
+    pbr::{
+        tonemapping_pipeline_key, MeshPipeline, MeshPipelineKey, PrepassPipeline, RenderMeshInstances,
+        SetMeshBindGroup, SetMeshViewBindGroup, SetPrepassViewBindGroup, Shadow, ShadowBinKey,
+    },
+    prelude::*,
+    render::{
+        extract_component::{ExtractComponent, ExtractComponentPlugin},
+        mesh::{allocator::MeshAllocator, MeshVertexBufferLayoutRef, RenderMesh, RenderMeshBufferInfo},
+        render_asset::RenderAssets,
+        render_phase::{
+            AddRenderCommand, BinnedRenderPhaseType, DrawFunctions, PhaseItem, PhaseItemExtraIndex,
+            RenderCommand, RenderCommandResult, SetItemPipeline, TrackedRenderPass,
+            ViewBinnedRenderPhases, ViewSortedRenderPhases,
+        },
+        render_resource::*,
+        renderer::{RenderDevice, RenderQueue},
+        sync_world::MainEntity,
+        view::ExtractedView,
+        Render, RenderApp, RenderSet,
+    },
+};
+
+use super::structures::{VegetationBatch, VegetationInstance};
+
+// const's
+/// path to vegetation shader
+const VEGETATION_SHADER: &str = "shaders/vegetation.wgsl";
+/// path to shader of vegetation's shadows
+const VEGETATION_SHADOW_SHADER: &str = "shaders/vegetation_shadow.wgsl";
+
+pub struct VegetationRenderPlugin;
+impl Plugin for VegetationRenderPlugin {
+    fn build(&self, app: &mut App) {
+        // only visible batches are sent to render world
+        app.add_plugins(ExtractComponentPlugin::<VegetationBatch>::extract_visible());
+
+        app.sub_app_mut(RenderApp)
+            .add_render_command::<Transparent3d, DrawVegetation>()
+            .add_render_command::<Shadow, DrawVegetationShadow>()
+            .init_resource::<SpecializedMeshPipelines<VegetationPipeline>>()
+            .init_resource::<SpecializedMeshPipelines<VegetationShadowPipeline>>()
+            .add_systems(Render, (
+                queue_vegetation.in_set(RenderSet::QueueMeshes),
+                queue_vegetation_shadows.in_set(RenderSet::QueueMeshes),
+                prepare_vegetation_buffers.in_set(RenderSet::PrepareResources),
+            ));
+    }
+
+    fn finish(&self, app: &mut App) {
+        app.sub_app_mut(RenderApp)
+            .init_resource::<VegetationPipeline>()
+            .init_resource::<VegetationShadowPipeline>();
+    }
+}
+
+impl ExtractComponent for VegetationBatch {
+    type QueryData = &'static VegetationBatch;
+    type QueryFilter = ();
+    type Out = Self;
+
+    fn extract_component(batch: QueryItem<'_, Self::QueryData>) -> Option<Self> {
+        (!batch.is_empty()).then(|| batch.clone())
+    }
+}
+
+/// Buffer of batch's instances
+#[derive(Component)]
+pub struct VegetationBuffer {
+    buffer: Buffer,
+    length: usize,
+    /// generation of batch, which is written to buffer
+    generation: u32,
+}
+
+/// Pipeline of vegetation, it's mesh pipeline with instance buffer
+#[derive(Resource)]
+pub struct VegetationPipeline {
+    shader: Handle<Shader>,
+    mesh_pipeline: MeshPipeline,
+}
+
+impl FromWorld for VegetationPipeline {
+    fn from_world(world: &mut World) -> Self {
+        Self {
+            shader: world.load_asset(VEGETATION_SHADER),
+            mesh_pipeline: world.resource::<MeshPipeline>().clone(),
+        }
+    }
+}
+
+impl SpecializedMeshPipeline for VegetationPipeline {
+    type Key = MeshPipelineKey;
+
+    fn specialize(
+        &self,
+        key: Self::Key,
+        layout: &MeshVertexBufferLayoutRef,
+    ) -> Result<RenderPipelineDescriptor, SpecializedMeshPipelineError> {
+        let mut descriptor = self.mesh_pipeline.specialize(key, layout)?;
+
+        descriptor.vertex.shader = self.shader.clone();
+        descriptor.vertex.buffers.push(instance_layout());
+
+        let fragment = descriptor.fragment.as_mut().unwrap();
+        fragment.shader = self.shader.clone();
+        fragment.shader_defs.push("DISTANCE_FOG".into());
+
+        Ok(descriptor)
+    }
+}
+
+/// Pipeline of vegetation's shadows, it writes only depth
+#[derive(Resource)]
+pub struct VegetationShadowPipeline {
+    shader: Handle<Shader>,
+    view_layout: BindGroupLayout,
+}
+
+impl FromWorld for VegetationShadowPipeline {
+    fn from_world(world: &mut World) -> Self {
+        // shadow views have only prepass's view bind group
+        let view_layout = world.resource::<PrepassPipeline<StandardMaterial>>()
+            .internal.view_layout_no_motion_vectors.clone();
+
+        Self {
+            shader: world.load_asset(VEGETATION_SHADOW_SHADER),
+            view_layout,
+        }
+    }
+}
+
+impl SpecializedMeshPipeline for VegetationShadowPipeline {
+    type Key = MeshPipelineKey;
+
+    fn specialize(
+        &self,
+        key: Self::Key,
+        layout: &MeshVertexBufferLayoutRef,
+    ) -> Result<RenderPipelineDescriptor, SpecializedMeshPipelineError> {
+        let vertex_layout = layout.0.get_layout(&[Mesh::ATTRIBUTE_POSITION.at_shader_location(0)])?;
+
+        Ok(RenderPipelineDescriptor {
+            label: Some("vegetation shadow pipeline".into()),
+            layout: vec![self.view_layout.clone()],
+            push_constant_ranges: Vec::new(),
+            vertex: VertexState {
+                shader: self.shader.clone(),
+                shader_defs: Vec::new(),
+                entry_point: "vertex".into(),
+                buffers: vec![vertex_layout, instance_layout()],
+            },
+            fragment: None,
+            // both sides, so thin crowns cast shadow from any light
+            primitive: PrimitiveState {
+                topology: key.primitive_topology(),
+                cull_mode: None,
+                ..default()
+            },
+            // the same depth as bevy's shadow pass has (reversed Z)
+            depth_stencil: Some(DepthStencilState {
+                format: CORE_3D_DEPTH_FORMAT,
+                depth_write_enabled: true,
+                depth_compare: CompareFunction::GreaterEqual,
+                stencil: StencilState::default(),
+                bias: DepthBiasState::default(),
+            }),
+            multisample: MultisampleState::default(),
+            zero_initialize_workgroup_memory: false,
+        })
+    }
+}
+
+/// add visible batches to render phase
+#[allow(clippy::too_many_arguments)]
+fn queue_vegetation(
+    mut pipelines: ResMut<SpecializedMeshPipelines<VegetationPipeline>>,
+    mut phases: ResMut<ViewSortedRenderPhases<Transparent3d>>,
+    draw_functions: Res<DrawFunctions<Transparent3d>>,
+    vegetation_pipeline: Res<VegetationPipeline>,
+    pipeline_cache: Res<PipelineCache>,
+    meshes: Res<RenderAssets<RenderMesh>>,
+    mesh_instances: Res<RenderMeshInstances>,
+    batches_query: Query<(Entity, &MainEntity), With<VegetationBatch>>,
+    views_query: Query<(Entity, &ExtractedView, &Msaa, Option<&Tonemapping>, Option<&DebandDither>)>,
+) {
+    let draw_vegetation = draw_functions.read().id::<DrawVegetation>();
+
+    for (view_entity, view, msaa, tonemapping, dither) in views_query.iter() {
+        let Some(phase) = phases.get_mut(&view_entity) else { continue; };
+
+        // the same key as standard materials have
+        let mut view_key = MeshPipelineKey::from_msaa_samples(msaa.samples()) | MeshPipelineKey::from_hdr(view.hdr);
+        if !view.hdr {
+            if let Some(tonemapping) = tonemapping {
+                view_key |= MeshPipelineKey::TONEMAP_IN_SHADER | tonemapping_pipeline_key(*tonemapping);
+            }
+            if let Some(DebandDither::Enabled) = dither {
+                view_key |= MeshPipelineKey::DEBAND_DITHER;
+            }
+        }
+
+        let rangefinder = view.rangefinder3d();
+
+        for (entity, main_entity) in batches_query.iter() {
+            let Some(mesh_instance) = mesh_instances.render_mesh_queue_data(*main_entity) else { continue; };
+            let Some(mesh) = meshes.get(mesh_instance.mesh_asset_id) else { continue; };
+
+            let key = view_key | MeshPipelineKey::from_primitive_topology(mesh.primitive_topology());
+            let pipeline = match pipelines.specialize(&pipeline_cache, &vegetation_pipeline, key, &mesh.layout) {
+                Ok(pipeline) => pipeline,
+                Err(error) => {
+                    error!("can't specialize vegetation pipeline: {error}");
+                    continue;
+                },
+            };
+
+            phase.add(Transparent3d {
+                entity: (entity, *main_entity),
+                pipeline,
+                draw_function: draw_vegetation,
+                distance: rangefinder.distance_translation(&mesh_instance.translation),
+                batch_range: 0..1,
+                extra_index: PhaseItemExtraIndex::NONE,
+            });
+        }
+    }
+}
+
+/// add batches to shadow maps of all lights, which cast shadows
+fn queue_vegetation_shadows(
+    mut pipelines: ResMut<SpecializedMeshPipelines<VegetationShadowPipeline>>,
+    mut phases: ResMut<ViewBinnedRenderPhases<Shadow>>,
+    draw_functions: Res<DrawFunctions<Shadow>>,
+    shadow_pipeline: Res<VegetationShadowPipeline>,
+    pipeline_cache: Res<PipelineCache>,
+    meshes: Res<RenderAssets<RenderMesh>>,
+    mesh_instances: Res<RenderMeshInstances>,
+    batches_query: Query<(Entity, &MainEntity), With<VegetationBatch>>,
+) {
+    let draw_shadow = draw_functions.read().id::<DrawVegetationShadow>();
+
+    for (entity, main_entity) in batches_query.iter() {
+        let Some(mesh_instance) = mesh_instances.render_mesh_queue_data(*main_entity) else { continue; };
+        let Some(mesh) = meshes.get(mesh_instance.mesh_asset_id) else { continue; };
+
+        let key = MeshPipelineKey::from_primitive_topology(mesh.primitive_topology());
+        let pipeline = match pipelines.specialize(&pipeline_cache, &shadow_pipeline, key, &mesh.layout) {
+            Ok(pipeline) => pipeline,
+            Err(error) => {
+                error!("can't specialize vegetation shadow pipeline: {error}");
+                continue;
+            },
+        };
+
+        // every shadow view is one cascade or cubemap face of light
+        for phase in phases.values_mut() {
+            phase.add(
+                ShadowBinKey { draw_function: draw_shadow, pipeline, asset_id: mesh_instance.mesh_asset_id.into() },
+                (entity, *main_entity),
+                BinnedRenderPhaseType::UnbatchableMesh,
+            );
+        }
+    }
+}
+
+/// write instances of batches to GPU buffers, buffer is
+/// written only after batch was rebuilt and grows only if needed
+fn prepare_vegetation_buffers(
+    mut commands: Commands,
+    mut batches_query: Query<(Entity, &VegetationBatch, Option<&mut VegetationBuffer>)>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    for (entity, batch, buffer) in batches_query.iter_mut() {
+        let contents: &[u8] = bytemuck::cast_slice(&batch.instances);
+
+        match buffer {
+            Some(buffer) if buffer.generation == batch.generation => {},
+            Some(mut buffer) if buffer.buffer.size() >= contents.len() as u64 => {
+                render_queue.write_buffer(&buffer.buffer, 0, contents);
+                buffer.length = batch.len();
+                buffer.generation = batch.generation;
+            },
+            _ => {
+                let buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+                    label: Some("vegetation instance buffer"),
+                    contents,
+                    usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+                });
+
+                commands.entity(entity).insert(VegetationBuffer {
+                    buffer,
+                    length: batch.len(),
+                    generation: batch.generation,
+                });
+            },
+        }
+    }
+}
+
+/// Draw commands of vegetation
+type DrawVegetation = (
+    SetItemPipeline,
+    SetMeshViewBindGroup<0>,
+    SetMeshBindGroup<1>,
+    DrawMeshInstanced,
+);
+
+/// Draw commands of vegetation's shadows
+type DrawVegetationShadow = (
+    SetItemPipeline,
+    SetPrepassViewBindGroup<0>,
+    DrawMeshInstanced,
+);
+
+/// Draw mesh of batch for every instance
+pub struct DrawMeshInstanced;
+impl<P: PhaseItem> RenderCommand<P> for DrawMeshInstanced {
+    type Param = (SRes<RenderAssets<RenderMesh>>, SRes<RenderMeshInstances>, SRes<MeshAllocator>);
+    type ViewQuery = ();
+    type ItemQuery = Read<VegetationBuffer>;
+
+    #[inline]
+    fn render<'w>(
+        item: &P,
+        _view: (),
+        instance_buffer: Option<&'w VegetationBuffer>,
+        (meshes, mesh_instances, mesh_allocator): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let mesh_allocator = mesh_allocator.into_inner();
+
+        let Some(mesh_instance) = mesh_instances.render_mesh_queue_data(item.main_entity()) else {
+            return RenderCommandResult::Skip;
+        };
+        let Some(gpu_mesh) = meshes.into_inner().get(mesh_instance.mesh_asset_id) else {
+            return RenderCommandResult::Skip;
+        };
+        let Some(instance_buffer) = instance_buffer else {
+            return RenderCommandResult::Skip;
+        };
+        let Some(vertex_slice) = mesh_allocator.mesh_vertex_slice(&mesh_instance.mesh_asset_id) else {
+            return RenderCommandResult::Skip;
+        };
+
+        pass.set_vertex_buffer(0, vertex_slice.buffer.slice(..));
+        pass.set_vertex_buffer(1, instance_buffer.buffer.slice(..));
+
+        let instances = 0..instance_buffer.length as u32;
+        match &gpu_mesh.buffer_info {
+            RenderMeshBufferInfo::Indexed { index_format, count } => {
+                let Some(index_slice) = mesh_allocator.mesh_index_slice(&mesh_instance.mesh_asset_id) else {
+                    return RenderCommandResult::Skip;
+                };
+
+                pass.set_index_buffer(index_slice.buffer.slice(..), 0, *index_format);
+                pass.draw_indexed(
+                    index_slice.range.start..(index_slice.range.start + count),
+                    vertex_slice.range.start as i32,
+                    instances,
+                );
+            },
+            RenderMeshBufferInfo::NonIndexed => {
+                pass.draw(vertex_slice.range, instances);
+            },
+        }
+
+        RenderCommandResult::Success
+    }
+}
+
+// helper functions
+/// Return layout of instance buffer, locations are after all mesh's attributes
+#[inline]
+fn instance_layout() -> VertexBufferLayout {
+    VertexBufferLayout {
+        array_stride: std::mem::size_of::<VegetationInstance>() as u64,
+        step_mode: VertexStepMode::Instance,
+        attributes: vec![
+            VertexAttribute { format: VertexFormat::Float32x4, offset: 0, shader_location: 8 },
+            VertexAttribute { format: VertexFormat::Float32x4, offset: 16, shader_location: 9 },
+            VertexAttribute { format: VertexFormat::Float32x4, offset: 32, shader_location: 10 },
+        ],
+    }
+}
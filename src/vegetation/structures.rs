@@ -0,0 +1,188 @@
+//! This module store vegetation's structures
+use std::sync::Arc;
+
+use bevy::prelude::*;
+use bytemuck::{Pod, Zeroable};
+use educe::Educe;
+
+// const's
+/// count of LOD levels of vegetation
+pub(super) const VEGETATION_LODS: usize = 2;
+
+/// Layer of vegetation, every layer has own meshes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VegetationLayer {
+    /// trunk of tree
+    Trunk,
+    /// crown of tree
+    Crown,
+    /// bushes and grass
+    Undergrowth,
+}
+
+impl VegetationLayer {
+    /// All layers
+    pub const ALL: [VegetationLayer; 3] = [VegetationLayer::Trunk, VegetationLayer::Crown, VegetationLayer::Undergrowth];
+
+    /// Return how strong layer sways by wind
+    pub fn wind(self) -> f32 {
+        match self {
+            VegetationLayer::Trunk => 0.005,
+            VegetationLayer::Crown => 0.03,
+            VegetationLayer::Undergrowth => 0.12,
+        }
+    }
+
+    /// Return true if layer is part of tree
+    pub fn is_tree(self) -> bool {
+        matches!(self, VegetationLayer::Trunk | VegetationLayer::Crown)
+    }
+}
+
+/// Instance of vegetation in GPU buffer
+#[derive(Clone, Copy, Debug, Pod, Zeroable)]
+#[repr(C)]
+pub struct VegetationInstance {
+    /// position in world (xyz) and scale (w)
+    pub position_scale: Vec4,
+    /// color of instance
+    pub color: Vec4,
+    /// rotation around Y, wind strength, distance of fade start and end
+    pub params: Vec4,
+}
+
+impl VegetationInstance {
+    /// Create instance, color and params are set by layer
+    pub fn new(position: Vec3, rotation: f32, scale: f32) -> Self {
+        Self {
+            position_scale: position.extend(scale),
+            color: Vec4::ONE,
+            params: Vec4::new(rotation, 0.0, 0.0, 0.0),
+        }
+    }
+
+    /// Return position in world
+    pub fn position(&self) -> Vec3 {
+        self.position_scale.truncate()
+    }
+}
+
+/// Vegetation of forest chunk, it's split to batches by distance
+#[derive(Component)]
+pub struct VegetationSource {
+    pub layer: VegetationLayer,
+    /// all instances of layer in chunk
+    pub instances: Vec<VegetationInstance>,
+}
+
+impl VegetationSource {
+    /// Create vegetation of layer
+    pub fn new(layer: VegetationLayer, instances: Vec<VegetationInstance>) -> Self {
+        Self { layer, instances }
+    }
+}
+
+/// Batches of source by LOD
+#[derive(Component)]
+pub struct VegetationBatches(pub [Entity; VEGETATION_LODS]);
+
+/// Instances, which are drawn by one draw call.
+/// Instances are shared with render world, so extraction doesn't copy them,
+/// generation is changed on every rebuild, so GPU buffer is written only then
+#[derive(Component, Clone, Default, Deref)]
+pub struct VegetationBatch {
+    #[deref]
+    pub instances: Arc<[VegetationInstance]>,
+    pub generation: u32,
+}
+
+impl VegetationBatch {
+    /// Replace instances of batch
+    pub fn set(&mut self, instances: Vec<VegetationInstance>) {
+        self.instances = instances.into();
+        self.generation = self.generation.wrapping_add(1);
+    }
+}
+
+/// Shared meshes of vegetation
+#[derive(Resource)]
+pub struct VegetationAssets {
+    pub trunk: [Handle<Mesh>; VEGETATION_LODS],
+    pub crown: [Handle<Mesh>; VEGETATION_LODS],
+    pub undergrowth: [Handle<Mesh>; VEGETATION_LODS],
+}
+
+impl VegetationAssets {
+    /// Return meshes of layer
+    pub fn meshes(&self, layer: VegetationLayer) -> &[Handle<Mesh>; VEGETATION_LODS] {
+        match layer {
+            VegetationLayer::Trunk => &self.trunk,
+            VegetationLayer::Crown => &self.crown,
+            VegetationLayer::Undergrowth => &self.undergrowth,
+        }
+    }
+}
+
+/// Settings of vegetation rendering
+#[derive(Resource, Clone, Debug, Educe, Reflect)]
+#[reflect(Resource)]
+#[educe(Default)]
+pub struct VegetationSettings {
+    #[educe(Default = 60.0)] /// distance, after which trees use simple meshes
+    pub tree_lod_distance: f32,
+
+    #[educe(Default = 220.0)] /// distance, after which trees aren't drawn
+    pub tree_max_distance: f32,
+
+    #[educe(Default = 15.0)] /// distance, after which undergrowth uses simple meshes
+    pub undergrowth_lod_distance: f32,
+
+    #[educe(Default = 40.0)] /// distance, after which undergrowth isn't drawn
+    pub undergrowth_max_distance: f32,
+
+    #[educe(Default = 10.0)] /// distance, on which instances fade before culling
+    pub fade_width: f32,
+
+    #[educe(Default = 4.0)] /// camera's movement, after which batches are rebuilt
+    pub rebuild_distance: f32,
+
+    #[educe(Default = Color::srgb(0.23, 0.17, 0.12))] /// color of trunks
+    pub trunk_color: Color,
+
+    #[educe(Default = Color::srgb(0.08, 0.16, 0.09))] /// color of crowns
+    pub crown_color: Color,
+
+    #[educe(Default = Color::srgb(0.12, 0.22, 0.1))] /// color of undergrowth
+    pub undergrowth_color: Color,
+}
+
+impl VegetationSettings {
+    /// Return LOD and max distances of layer
+    pub fn distances(&self, layer: VegetationLayer) -> (f32, f32) {
+        if layer.is_tree() {
+            (self.tree_lod_distance, self.tree_max_distance)
+        } else {
+            (self.undergrowth_lod_distance, self.undergrowth_max_distance)
+        }
+    }
+
+    /// Return color of layer
+    pub fn color(&self, layer: VegetationLayer) -> Color {
+        match layer {
+            VegetationLayer::Trunk => self.trunk_color,
+            VegetationLayer::Crown => self.crown_color,
+            VegetationLayer::Undergrowth => self.undergrowth_color,
+        }
+    }
+}
+
+/// Statistics of vegetation, they are shown in debug overlay
+#[derive(Resource, Default, Debug)]
+pub struct VegetationStats {
+    /// all instances
+    pub instances: usize,
+    /// instances in batches
+    pub drawn: usize,
+    /// not empty batches
+    pub batches: usize,
+}
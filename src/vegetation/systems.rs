@@ -0,0 +1,148 @@
+//! implementation of vegetation systems
+
+// import crates
+use bevy::{prelude::*, render::primitives::Aabb};
+
+// import data from this crate
+use crate::levels::structures::ForestChunk;
+use super::structures::*;
+
+/// create shared meshes of vegetation, second LOD has less polygons
+pub fn setup_vegetation_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    commands.insert_resource(VegetationAssets {
+        trunk: [
+            meshes.add(Cylinder::new(0.25, 6.0).mesh().resolution(10).build().translated_by(Vec3::Y * 3.0)),
+            meshes.add(Cylinder::new(0.25, 6.0).mesh().resolution(4).build().translated_by(Vec3::Y * 3.0)),
+        ],
+        crown: [
+            meshes.add(Cone { radius: 1.8, height: 5.0 }.mesh().resolution(16).build().translated_by(Vec3::Y * 6.0)),
+            meshes.add(Cone { radius: 1.8, height: 5.0 }.mesh().resolution(5).build().translated_by(Vec3::Y * 6.0)),
+        ],
+        undergrowth: [
+            meshes.add(Sphere::new(0.5).mesh().ico(1).unwrap().scaled_by(Vec3::new(1.0, 0.6, 1.0))),
+            meshes.add(Sphere::new(0.5).mesh().ico(0).unwrap().scaled_by(Vec3::new(1.0, 0.6, 1.0))),
+        ],
+    });
+}
+
+/// spawn batches of new vegetation sources
+pub fn setup_vegetation_batches(
+    mut commands: Commands,
+    sources_query: Query<(Entity, &VegetationSource), Added<VegetationSource>>,
+    vegetation_assets: Option<Res<VegetationAssets>>,
+) {
+    let Some(vegetation_assets) = vegetation_assets else { return; };
+
+    for (entity, source) in sources_query.iter() {
+        let meshes = vegetation_assets.meshes(source.layer);
+
+        let batches = std::array::from_fn(|lod| {
+            commands.spawn((
+                #[cfg(debug_assertions)] Name::new(format!("Vegetation {:?} LOD {lod}", source.layer)),
+                Mesh3d(meshes[lod].clone()),
+                Transform::default(),
+                Visibility::Hidden,
+                Aabb::default(),
+                VegetationBatch::default(),
+            )).set_parent(entity).id()
+        });
+
+        commands.entity(entity).insert(VegetationBatches(batches));
+    }
+}
+
+/// split instances of sources to batches by distance to camera,
+/// batches are rebuilt only after camera moved far enough
+pub fn update_vegetation_batches(
+    mut batches_query: Query<(&mut VegetationBatch, &mut Aabb, &mut Visibility)>,
+    mut stats: ResMut<VegetationStats>,
+    mut last_position: Local<Option<Vec3>>,
+    sources_query: Query<(&VegetationSource, &VegetationBatches, Option<&Parent>)>,
+    chunks_query: Query<&ForestChunk>,
+    new_sources: Query<(), Added<VegetationBatches>>,
+    cameras_query: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    settings: Res<VegetationSettings>,
+) {
+    let Some(camera_position) = cameras_query.iter()
+        .find(|(camera, _)| camera.is_active)
+        .map(|(_, transform)| transform.translation())
+    else {
+        return;
+    };
+
+    let moved = last_position.map_or(true, |last| last.distance(camera_position) > settings.rebuild_distance);
+    if !moved && new_sources.is_empty() && !settings.is_changed() {
+        return;
+    }
+    *last_position = Some(camera_position);
+
+    *stats = VegetationStats::default();
+
+    for (source, batches, parent) in sources_query.iter() {
+        // batches are in chunk's space, transforms of new batches aren't
+        // propagated yet, so origin is taken from chunk's coord
+        let origin = parent
+            .and_then(|parent| chunks_query.get(parent.get()).ok())
+            .map_or(Vec3::ZERO, |chunk| (chunk.coord.as_vec2() * chunk.size).extend(0.0).xzy());
+
+        let (lod_distance, max_distance) = settings.distances(source.layer);
+        let color = settings.color(source.layer).to_linear().to_vec4();
+        let params = Vec3::new(source.layer.wind(), max_distance - settings.fade_width, max_distance);
+
+        let mut lods: [Vec<VegetationInstance>; VEGETATION_LODS] = Default::default();
+        for instance in source.instances.iter() {
+            let distance = instance.position().distance(camera_position);
+            if distance > max_distance {
+                continue;
+            }
+
+            let lod = if distance > lod_distance { 1 } else { 0 };
+            lods[lod].push(VegetationInstance {
+                color: color * tint(instance.position()),
+                params: Vec4::new(instance.params.x, params.x, params.y, params.z),
+                ..*instance
+            });
+        }
+
+        stats.instances += source.instances.len();
+
+        for (lod, instances) in lods.into_iter().enumerate() {
+            let Ok((mut batch, mut aabb, mut visibility)) = batches_query.get_mut(batches.0[lod]) else {
+                continue;
+            };
+
+            stats.drawn += instances.len();
+            stats.batches += (!instances.is_empty()) as usize;
+
+            // aabb is in batch's space, it's used by frustum culling
+            let (min, max) = instances.iter().fold((Vec3::MAX, Vec3::MIN), |(min, max), instance| {
+                let position = instance.position() - origin;
+                (min.min(position), max.max(position))
+            });
+
+            if instances.is_empty() {
+                *visibility = Visibility::Hidden;
+            } else {
+                *aabb = Aabb::from_min_max(min - Vec3::new(3.0, 1.0, 3.0), max + Vec3::new(3.0, 14.0, 3.0));
+                *visibility = Visibility::Inherited;
+            }
+
+            // empty batch stays empty, so it isn't written to GPU again
+            if !(instances.is_empty() && batch.is_empty()) {
+                batch.set(instances);
+            }
+        }
+    }
+}
+
+// helper functions
+/// Return small variation of color, it depends only on position
+#[inline]
+fn tint(position: Vec3) -> Vec4 {
+    let hash = (position.x * 12.9898 + position.z * 78.233).sin() * 43758.547;
+    let value = 0.85 + hash.fract().abs() * 0.3;
+    Vec4::new(value, value, value, 1.0)
+}
@@ -0,0 +1,36 @@
+//! Weather - time of day with sun and moon, and weather states
+//! (clear, fog, rain, storm with lightning). Both can be forced
+//! by events and trigger actions ("weather:<kind>", "time:<hours>").
+
+// import crates
+use bevy::prelude::*;
+
+// weather systems
+pub(super) mod systems;
+pub mod structures;
+
+pub struct WeatherPlugin;
+impl Plugin for WeatherPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<structures::TimeOfDay>();
+        app.register_type::<structures::WeatherSettings>();
+        app.insert_resource(structures::TimeOfDay::default());
+        app.insert_resource(structures::WeatherSettings::default());
+        app.insert_resource(structures::WeatherEffects::default());
+
+        app.add_event::<structures::SetWeather>();
+        app.add_event::<structures::SetTimeOfDay>();
+
+        app.add_systems(Startup, systems::setup_weather);
+        app.add_systems(Update, (
+            systems::weather_triggers,
+            systems::apply_weather_events,
+            systems::update_time_of_day,
+            systems::update_weather,
+            systems::update_lightning,
+            systems::apply_sky,
+            systems::update_rain_sound,
+            systems::update_rain_drops,
+        ).chain().before(crate::atmosphere::systems::apply_atmosphere));
+    }
+}
@@ -0,0 +1,274 @@
+//! This module store structures of time of day and weather
+use bevy::prelude::*;
+use educe::Educe;
+
+use crate::random::SeededRng;
+
+// const's
+/// path to looped rain sound
+pub(super) const RAIN_SOUND: &str = "audio/weather/rain.ogg";
+/// paths to thunder sounds
+pub(super) const THUNDER_SOUNDS: [&str; 3] = [
+    "audio/weather/thunder_1.ogg", "audio/weather/thunder_2.ogg", "audio/weather/thunder_3.ogg",
+];
+/// illuminance of sun at clear day (lux)
+pub(super) const SUN_ILLUMINANCE: f32 = 3000.0;
+/// illuminance of moon at clear night (lux)
+pub(super) const MOON_ILLUMINANCE: f32 = 15.0;
+/// illuminance of lightning flash (lux)
+pub(super) const LIGHTNING_ILLUMINANCE: f32 = 20000.0;
+/// how fast lightning flash fades (per second)
+pub(super) const LIGHTNING_FADE_SPEED: f32 = 6.0;
+/// count of rain drops at full rain
+pub(super) const RAIN_DROPS: usize = 800;
+/// half size of box around camera, in which rain drops fall
+pub(super) const RAIN_AREA: Vec3 = Vec3::new(12.0, 8.0, 12.0);
+/// fall speed of rain drops (m/s)
+pub(super) const RAIN_SPEED: f32 = 14.0;
+
+/// Kind of weather
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+pub enum WeatherKind {
+    #[default]
+    Clear,
+    Fog,
+    Rain,
+    Storm,
+}
+
+impl WeatherKind {
+    /// All kinds of weather
+    pub const ALL: [WeatherKind; 4] = [WeatherKind::Clear, WeatherKind::Fog, WeatherKind::Rain, WeatherKind::Storm];
+
+    /// Return name of weather (used in trigger actions and console)
+    pub fn name(self) -> &'static str {
+        match self {
+            WeatherKind::Clear => "clear",
+            WeatherKind::Fog => "fog",
+            WeatherKind::Rain => "rain",
+            WeatherKind::Storm => "storm",
+        }
+    }
+
+    /// Return weather by name
+    pub fn from_name(name: &str) -> Option<WeatherKind> {
+        WeatherKind::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+
+    /// Return effects of weather
+    pub fn effects(self) -> WeatherEffects {
+        let (cloud_cover, fog_scale, rain, hearing_multiplier) = match self {
+            WeatherKind::Clear => (0.0, 1.0, 0.0, 1.0),
+            WeatherKind::Fog => (0.4, 3.0, 0.0, 1.0),
+            WeatherKind::Rain => (0.7, 1.6, 0.6, 0.7),
+            WeatherKind::Storm => (0.9, 1.8, 1.0, 0.5),
+        };
+
+        WeatherEffects { cloud_cover, fog_scale, rain, hearing_multiplier, storm: (self == WeatherKind::Storm) as u8 as f32 }
+    }
+}
+
+/// Effects of current weather, they are blended while weather changes
+#[derive(Resource, Clone, Copy, Debug, Educe)]
+#[educe(Default)]
+pub struct WeatherEffects {
+    /// how much sky is covered by clouds (0.0 - 1.0)
+    pub cloud_cover: f32,
+    #[educe(Default = 1.0)] /// multiplier of fog density
+    pub fog_scale: f32,
+    /// strength of rain (0.0 - 1.0)
+    pub rain: f32,
+    #[educe(Default = 1.0)]
+    /// multiplier of distance, at which monster hears player and starts chase,
+    /// rain and storm hide player's sounds
+    pub hearing_multiplier: f32,
+    /// strength of storm, lightning strikes only in storm (0.0 - 1.0)
+    pub storm: f32,
+}
+
+impl WeatherEffects {
+    /// Return effects between self and other
+    pub fn lerp(&self, other: &WeatherEffects, t: f32) -> WeatherEffects {
+        WeatherEffects {
+            cloud_cover: self.cloud_cover.lerp(other.cloud_cover, t),
+            fog_scale: self.fog_scale.lerp(other.fog_scale, t),
+            rain: self.rain.lerp(other.rain, t),
+            hearing_multiplier: self.hearing_multiplier.lerp(other.hearing_multiplier, t),
+            storm: self.storm.lerp(other.storm, t),
+        }
+    }
+}
+
+/// Time of day
+#[derive(Resource, Clone, Debug, Educe, Reflect)]
+#[reflect(Resource)]
+#[educe(Default)]
+pub struct TimeOfDay {
+    #[educe(Default = 23.0)] /// current hour (0.0 - 24.0)
+    pub hours: f32,
+
+    #[educe(Default = 1800.0)] /// length of full day (in seconds)
+    pub day_length: f32,
+
+    /// time doesn't go
+    pub paused: bool,
+}
+
+impl TimeOfDay {
+    /// Return direction to sun
+    pub fn sun_direction(&self) -> Vec3 {
+        // sun rises at 6:00 in east and sets at 18:00 in west
+        let angle = (self.hours - 6.0) / 24.0 * std::f32::consts::TAU;
+        Vec3::new(angle.cos(), angle.sin(), 0.3).normalize()
+    }
+
+    /// Return how much it's day: 0.0 - night, 1.0 - day
+    pub fn daylight(&self) -> f32 {
+        (self.sun_direction().y * 4.0 + 0.5).clamp(0.0, 1.0)
+    }
+}
+
+/// Settings of weather changes
+#[derive(Resource, Clone, Debug, Educe, Reflect)]
+#[reflect(Resource)]
+#[educe(Default)]
+pub struct WeatherSettings {
+    #[educe(Default = 0x0057_0123)] /// seed of weather's random
+    pub seed: u64,
+
+    #[educe(Default = 120.0)] /// minimal duration of weather (in seconds)
+    pub min_duration: f32,
+
+    #[educe(Default = 360.0)] /// maximal duration of weather (in seconds)
+    pub max_duration: f32,
+
+    #[educe(Default = 20.0)] /// time of transition between weathers (in seconds)
+    pub transition_time: f32,
+
+    #[educe(Default = [0.35, 0.3, 0.2, 0.15])] /// chances of clear, fog, rain and storm
+    pub chances: [f32; 4],
+
+    #[educe(Default = 6.0)] /// minimal time between lightning strikes (in seconds)
+    pub min_lightning_interval: f32,
+
+    #[educe(Default = 20.0)] /// maximal time between lightning strikes (in seconds)
+    pub max_lightning_interval: f32,
+
+    #[educe(Default = 4.0)] /// maximal delay of thunder after lightning (in seconds)
+    pub max_thunder_delay: f32,
+}
+
+/// State of weather
+#[derive(Resource)]
+pub struct Weather {
+    /// effects at start of transition
+    pub from: WeatherEffects,
+    pub current: WeatherKind,
+    /// progress of transition (0.0 - 1.0)
+    pub transition: f32,
+    /// weather is forced by script and doesn't change
+    pub forced: bool,
+    /// time before next change
+    pub next_change: f32,
+    /// brightness of lightning flash (0.0 - 1.0)
+    pub lightning: f32,
+    /// time before next lightning
+    pub next_lightning: f32,
+    /// time before thunder
+    pub thunder_in: Option<f32>,
+    pub rng: SeededRng,
+}
+
+impl Weather {
+    /// Start transition from current effects to other weather,
+    /// so weather changed in middle of transition doesn't jump
+    pub fn change(&mut self, kind: WeatherKind, effects: &WeatherEffects) {
+        if kind == self.current {
+            return;
+        }
+
+        self.from = *effects;
+        self.current = kind;
+        self.transition = 0.0;
+    }
+
+    /// Return effects at current progress of transition
+    pub fn effects(&self) -> WeatherEffects {
+        self.from.lerp(&self.current.effects(), self.transition)
+    }
+}
+
+/// Sounds of weather
+#[derive(Resource)]
+pub struct WeatherSounds {
+    pub thunder: Vec<Handle<AudioSource>>,
+}
+
+/// Event: change weather, forced weather doesn't change by itself
+#[derive(Event, Clone, Copy, Debug)]
+pub struct SetWeather {
+    /// new weather, none - return to automatic changes
+    pub kind: Option<WeatherKind>,
+    pub forced: bool,
+}
+
+/// Event: set time of day (in hours)
+#[derive(Event, Clone, Copy, Debug)]
+pub struct SetTimeOfDay(pub f32);
+
+/// Light of sun or moon
+#[derive(Component)]
+pub struct CelestialLight;
+
+/// Looped sound of rain
+#[derive(Component)]
+pub struct RainSound;
+
+/// Drop of rain, drops with index below rain's strength are shown
+#[derive(Component)]
+pub struct RainDrop(pub usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn weather() -> Weather {
+        Weather {
+            from: WeatherKind::Clear.effects(),
+            current: WeatherKind::Clear,
+            transition: 1.0,
+            forced: false,
+            next_change: 0.0,
+            lightning: 0.0,
+            next_lightning: 0.0,
+            thunder_in: None,
+            rng: SeededRng::new(0),
+        }
+    }
+
+    #[test]
+    fn change_in_transition_starts_from_current_effects() {
+        let mut weather = weather();
+        weather.change(WeatherKind::Storm, &weather.effects());
+        weather.transition = 0.5;
+
+        let effects = weather.effects();
+        weather.change(WeatherKind::Fog, &effects);
+
+        let start = weather.effects();
+        assert_eq!(start.rain, effects.rain);
+        assert_eq!(start.cloud_cover, effects.cloud_cover);
+        assert_eq!(start.hearing_multiplier, effects.hearing_multiplier);
+    }
+
+    #[test]
+    fn finished_transition_gives_effects_of_weather() {
+        let mut weather = weather();
+        weather.change(WeatherKind::Rain, &weather.effects());
+        weather.transition = 1.0;
+
+        let effects = weather.effects();
+        assert_eq!(effects.rain, WeatherKind::Rain.effects().rain);
+        assert_eq!(effects.hearing_multiplier, WeatherKind::Rain.effects().hearing_multiplier);
+    }
+}
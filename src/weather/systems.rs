@@ -0,0 +1,311 @@
+//! implementation of weather systems
+
+// import crates
+use bevy::{audio::PlaybackMode, pbr::NotShadowCaster, prelude::*};
+
+// import data from this crate
+use crate::atmosphere::structures::AtmosphereModifiers;
+use crate::audio::structures::{AudioEmitter, AudioMixer, LoadSound, MixerBus};
+use crate::random::SeededRng;
+use crate::triggers::structures::TriggerEntered;
+use super::structures::*;
+
+/// create sun/moon light, rain drops, rain sound and weather state
+pub fn setup_weather(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    settings: Res<WeatherSettings>,
+    mixer: Res<AudioMixer>,
+    assets: Res<AssetServer>,
+) {
+    let mut rng = SeededRng::new(settings.seed);
+    let next_change = rng.range(settings.min_duration, settings.max_duration);
+
+    commands.insert_resource(Weather {
+        from: WeatherKind::Clear.effects(),
+        current: WeatherKind::Clear,
+        transition: 1.0,
+        forced: false,
+        next_change,
+        lightning: 0.0,
+        next_lightning: settings.max_lightning_interval,
+        thunder_in: None,
+        rng,
+    });
+
+    commands.insert_resource(WeatherSounds {
+        thunder: THUNDER_SOUNDS.iter().filter_map(|path| assets.load_sound(*path)).collect(),
+    });
+
+    commands.spawn((
+        #[cfg(debug_assertions)] Name::new("Celestial light"),
+        DirectionalLight { shadows_enabled: true, ..default() },
+        Transform::default(),
+        CelestialLight,
+    ));
+
+    // drops are scattered in box around camera, they are hidden without rain
+    let mut drops_rng = SeededRng::new(settings.seed ^ 0xD209);
+    let drop_mesh = meshes.add(Cuboid::new(0.01, 0.45, 0.01));
+    let drop_material = materials.add(StandardMaterial {
+        base_color: Color::srgba(0.7, 0.75, 0.85, 0.35),
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        ..default()
+    });
+
+    for index in 0..RAIN_DROPS {
+        let position = Vec3::new(
+            drops_rng.range(-RAIN_AREA.x, RAIN_AREA.x),
+            drops_rng.range(-RAIN_AREA.y, RAIN_AREA.y),
+            drops_rng.range(-RAIN_AREA.z, RAIN_AREA.z),
+        );
+
+        commands.spawn((
+            Mesh3d(drop_mesh.clone()),
+            MeshMaterial3d(drop_material.clone()),
+            Transform::from_translation(position),
+            Visibility::Hidden,
+            NotShadowCaster,
+            RainDrop(index),
+        ));
+    }
+
+    commands.spawn((
+        #[cfg(debug_assertions)] Name::new("Rain"),
+        AudioEmitter::new(MixerBus::Ambience, 0.0).sound(
+            assets.load_sound(RAIN_SOUND).unwrap_or_default(), PlaybackMode::Loop, false, &mixer
+        ),
+        RainSound,
+    ));
+}
+
+/// force weather and time by triggers with actions
+/// "weather:<kind>", "weather:auto" and "time:<hours>"
+pub fn weather_triggers(
+    mut entered: EventReader<TriggerEntered>,
+    mut set_weather: EventWriter<SetWeather>,
+    mut set_time: EventWriter<SetTimeOfDay>,
+) {
+    for event in entered.read() {
+        if let Some(name) = event.argument("weather") {
+            match (name, WeatherKind::from_name(name)) {
+                ("auto", _) => { set_weather.send(SetWeather { kind: None, forced: false }); },
+                (_, Some(kind)) => { set_weather.send(SetWeather { kind: Some(kind), forced: true }); },
+                _ => warn!("unknown weather \"{name}\" in trigger"),
+            }
+        }
+
+        if let Some(hours) = event.argument("time") {
+            // "inf" and "NaN" are parsed too, but they break the clock
+            match hours.parse::<f32>().ok().filter(|hours| hours.is_finite()) {
+                Some(hours) => { set_time.send(SetTimeOfDay(hours)); },
+                None => warn!("wrong time \"{hours}\" in trigger"),
+            }
+        }
+    }
+}
+
+/// apply scripted changes of weather and time
+pub fn apply_weather_events(
+    mut set_weather: EventReader<SetWeather>,
+    mut set_time: EventReader<SetTimeOfDay>,
+    mut time_of_day: ResMut<TimeOfDay>,
+    mut weather: ResMut<Weather>,
+    effects: Res<WeatherEffects>,
+) {
+    for event in set_weather.read() {
+        weather.forced = event.forced;
+
+        if let Some(kind) = event.kind {
+            weather.change(kind, &effects);
+        }
+    }
+
+    for SetTimeOfDay(hours) in set_time.read() {
+        time_of_day.hours = hours.rem_euclid(24.0);
+    }
+}
+
+/// move time of day
+pub fn update_time_of_day(
+    mut time_of_day: ResMut<TimeOfDay>,
+    time: Res<Time>,
+) {
+    if time_of_day.paused || time_of_day.day_length <= 0.0 {
+        return;
+    }
+
+    let hours = time_of_day.hours + time.delta_secs() / time_of_day.day_length * 24.0;
+    time_of_day.hours = hours.rem_euclid(24.0);
+}
+
+/// change weather by chances and blend it's effects
+pub fn update_weather(
+    mut weather: ResMut<Weather>,
+    mut effects: ResMut<WeatherEffects>,
+    settings: Res<WeatherSettings>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_secs();
+
+    // automatic change of weather
+    if !weather.forced {
+        weather.next_change -= delta;
+
+        if weather.next_change <= 0.0 {
+            weather.next_change = weather.rng.range(settings.min_duration, settings.max_duration);
+
+            let kind = choose_weather(&mut weather.rng, &settings.chances);
+            weather.change(kind, &effects);
+        }
+    }
+
+    weather.transition = if settings.transition_time > 0.0 {
+        (weather.transition + delta / settings.transition_time).min(1.0)
+    } else {
+        1.0
+    };
+
+    *effects = weather.effects();
+}
+
+/// strike lightning in storm, thunder is heard after delay
+pub fn update_lightning(
+    mut commands: Commands,
+    mut weather: ResMut<Weather>,
+    effects: Res<WeatherEffects>,
+    settings: Res<WeatherSettings>,
+    sounds: Res<WeatherSounds>,
+    mixer: Res<AudioMixer>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_secs();
+    weather.lightning = (weather.lightning - LIGHTNING_FADE_SPEED * delta).max(0.0);
+
+    // thunder
+    if let Some(thunder_in) = weather.thunder_in.as_mut() {
+        *thunder_in -= delta;
+
+        if *thunder_in <= 0.0 {
+            weather.thunder_in = None;
+
+            let index = weather.rng.index(sounds.thunder.len());
+            if let Some(sound) = sounds.thunder.get(index) {
+                commands.spawn((
+                    #[cfg(debug_assertions)] Name::new("Thunder"),
+                    AudioEmitter::new(MixerBus::Ambience, effects.storm).sound(
+                        sound.clone(), PlaybackMode::Despawn, false, &mixer
+                    ),
+                ));
+            }
+        }
+    }
+
+    if effects.storm <= 0.0 {
+        return;
+    }
+
+    // lightning strikes more often at full storm
+    weather.next_lightning -= delta * effects.storm;
+    if weather.next_lightning > 0.0 {
+        return;
+    }
+
+    weather.next_lightning = weather.rng.range(settings.min_lightning_interval, settings.max_lightning_interval);
+    weather.lightning = weather.rng.range(0.6, 1.0);
+    weather.thunder_in = Some(weather.rng.range(0.5, settings.max_thunder_delay));
+}
+
+/// move sun and moon, set sky color and darkness of atmosphere
+pub fn apply_sky(
+    mut light_query: Query<(&mut DirectionalLight, &mut Transform), With<CelestialLight>>,
+    mut modifiers: ResMut<AtmosphereModifiers>,
+    mut clear_color: ResMut<ClearColor>,
+    time_of_day: Res<TimeOfDay>,
+    effects: Res<WeatherEffects>,
+    weather: Res<Weather>,
+) {
+    let daylight = time_of_day.daylight();
+    let clouds = 1.0 - effects.cloud_cover * 0.8;
+
+    // light comes from sun at day and from moon at night
+    let sun = time_of_day.sun_direction();
+    let direction = if sun.y > 0.0 { sun } else { -sun };
+
+    for (mut light, mut transform) in light_query.iter_mut() {
+        let illuminance = MOON_ILLUMINANCE.lerp(SUN_ILLUMINANCE, daylight) * clouds;
+        light.illuminance = illuminance + LIGHTNING_ILLUMINANCE * weather.lightning;
+        light.color = Color::srgb(0.6, 0.7, 1.0).mix(&Color::srgb(1.0, 0.95, 0.85), daylight);
+        *transform = Transform::default().looking_to(-direction, Vec3::Y);
+    }
+
+    // sky
+    let night_sky = LinearRgba::rgb(0.004, 0.005, 0.01);
+    let day_sky = LinearRgba::rgb(0.25, 0.35, 0.5);
+    let sky = night_sky.mix(&day_sky, daylight) * clouds + LinearRgba::rgb(0.4, 0.45, 0.6) * weather.lightning;
+    clear_color.0 = sky.into();
+
+    // day is brighter, clouds make it darker, lightning lights everything
+    modifiers.ambient_scale = 1.0_f32.lerp(4.0, daylight) * (1.0 - effects.cloud_cover * 0.5) + weather.lightning * 8.0;
+    modifiers.fog_density_scale = effects.fog_scale;
+    modifiers.volumetric_scale = effects.fog_scale;
+}
+
+/// change volume of rain
+pub fn update_rain_sound(
+    mut rain_query: Query<&mut AudioEmitter, With<RainSound>>,
+    effects: Res<WeatherEffects>,
+) {
+    for mut emitter in rain_query.iter_mut() {
+        emitter.volume = effects.rain;
+    }
+}
+
+/// move rain drops down around camera, count of shown drops depends on rain
+pub fn update_rain_drops(
+    mut drops_query: Query<(&RainDrop, &mut Transform, &mut Visibility)>,
+    cameras_query: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    effects: Res<WeatherEffects>,
+    time: Res<Time>,
+) {
+    let Some(center) = cameras_query.iter()
+        .find(|(camera, _)| camera.is_active)
+        .map(|(_, transform)| transform.translation())
+    else {
+        return;
+    };
+
+    let shown = (effects.rain * RAIN_DROPS as f32) as usize;
+    let fall = RAIN_SPEED * time.delta_secs();
+
+    for (RainDrop(index), mut transform, mut visibility) in drops_query.iter_mut() {
+        if *index >= shown {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        }
+        visibility.set_if_neq(Visibility::Inherited);
+
+        // drop, which leaves box, appears from other side, so rain stays in world
+        let local = transform.translation - center - Vec3::Y * fall;
+        transform.translation = center + (local + RAIN_AREA).rem_euclid(RAIN_AREA * 2.0) - RAIN_AREA;
+    }
+}
+
+// helper functions
+/// Choose weather by chances
+#[inline]
+fn choose_weather(rng: &mut SeededRng, chances: &[f32; 4]) -> WeatherKind {
+    let total: f32 = chances.iter().sum();
+    let mut roll = rng.range(0.0, total.max(f32::EPSILON));
+
+    for (kind, chance) in WeatherKind::ALL.into_iter().zip(chances) {
+        if roll < *chance {
+            return kind;
+        }
+        roll -= chance;
+    }
+
+    WeatherKind::Clear
+}